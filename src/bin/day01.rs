@@ -4,28 +4,20 @@
 //! The structure is: read → parse → solve part 1 & part 2.
 
 use aoc2025::*;
-use std::env;
 
 struct Day01;
 
 impl AoCDay for Day01 {
-    type Parsed = Vec<i32>;
-
-    fn parse(input: &str) -> Self::Parsed {
-        lines(input)
-            .map(|line| {
-                let (dir, num) = line.split_at(1);
-                let n = num.parse::<i32>().unwrap();
-                match dir {
-                    "L" => -n,
-                    "R" => n,
-                    _ => panic!("Unknown direction: {}", dir),
-                }
-            })
-            .collect()
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = Vec<i32>;
+    type Params = ();
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        lines(input).map(parse_rotation).collect()
     }
 
-    fn part1(data: &Self::Parsed) -> String {
+    fn part1(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
         let sum = data
             .iter()
             .fold((0, 50), |c, m| {
@@ -40,7 +32,7 @@ impl AoCDay for Day01 {
         format!("{}", sum)
     }
 
-    fn part2(data: &Self::Parsed) -> String {
+    fn part2(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
         let sum = data
             .iter()
             .fold((0, 50), |(sum, pos), &m| {
@@ -53,6 +45,36 @@ impl AoCDay for Day01 {
     }
 }
 
+impl StreamingDay for Day01 {
+    /// Zero landings (part 1), zero passes (part 2) and the dial position.
+    type Acc = (i64, i64, i32);
+
+    fn init(_params: &Self::Params) -> Self::Acc {
+        (0, 0, 50)
+    }
+
+    fn fold_line(acc: Self::Acc, line: &str, _params: &Self::Params) -> Self::Acc {
+        let (landings, passes, pos) = acc;
+        let (hits, new_pos) = eval_move(pos, parse_rotation(line));
+        (landings + i64::from(new_pos == 0), passes + hits, new_pos)
+    }
+
+    fn finish(acc: Self::Acc, _params: &Self::Params) -> (String, String) {
+        let (landings, passes, _) = acc;
+        (landings.to_string(), passes.to_string())
+    }
+}
+
+fn parse_rotation(line: &str) -> i32 {
+    let (dir, num) = line.split_at(1);
+    let n = num.parse::<i32>().unwrap();
+    match dir {
+        "L" => -n,
+        "R" => n,
+        _ => panic!("Unknown direction: {}", dir),
+    }
+}
+
 fn eval_move(pos: i32, delta: i32) -> (i64, i32) {
     if delta == 0 {
         return (0, pos);
@@ -82,21 +104,7 @@ fn eval_move(pos: i32, delta: i32) -> (i64, i32) {
     (hits, new_pos)
 }
 
-fn main() {
-    // Allow: `cargo run --bin day01` or specify an input: `cargo run --bin day01 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day01.txt");
-
-    let raw = read_input(input_path);
-    let parsed = Day01::parse(&raw);
-
-    println!("🎄 Day 01 — Advent of Code 2025");
-    println!("⭐ Part 1: {}", Day01::part1(&parsed));
-    println!("⭐ Part 2: {}", Day01::part2(&parsed));
-}
+aoc_main!(Day01, stream);
 
 #[cfg(test)]
 mod tests {
@@ -115,15 +123,11 @@ R14
 L82
 "#;
 
-    #[test]
-    fn test_part1() {
-        let parsed = Day01::parse(SAMPLE);
-        assert_eq!(Day01::part1(&parsed), "3");
-    }
+    aoc_tests!(Day01, [(SAMPLE, "3", "6")]);
 
     #[test]
-    fn test_part2() {
-        let parsed = Day01::parse(SAMPLE);
-        assert_eq!(Day01::part2(&parsed), "6");
+    fn test_stream() {
+        let answers = solve_stream::<Day01>(SAMPLE.as_bytes(), &()).unwrap();
+        assert_eq!(answers, ("3".to_string(), "6".to_string()));
     }
 }
@@ -5,22 +5,24 @@
 
 use aoc2025::*;
 use std::collections::HashSet;
-use std::env;
 
 struct Day02;
 
 impl AoCDay for Day02 {
-    type Parsed = Vec<(u64, u64)>;
+    const DAY: u8 = 2;
 
-    fn parse(input: &str) -> Self::Parsed {
+    type Parsed<'a> = Vec<(u64, u64)>;
+    type Params = ();
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
         input
-            .split(|c| c == ',')
-            .map(|e| e.trim().split_once(|e| e == '-').unwrap())
+            .split(',')
+            .map(|e| e.trim().split_once('-').unwrap())
             .map(|(from, to)| (from.parse::<u64>().unwrap(), to.parse::<u64>().unwrap()))
             .collect()
     }
 
-    fn part1(data: &Self::Parsed) -> String {
+    fn part1(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
         let sum = data.iter().fold(0, |acc, &(start, end)| {
             let ids = calc_twice(start, end);
             println!(
@@ -37,7 +39,7 @@ impl AoCDay for Day02 {
         format!("{}", sum)
     }
 
-    fn part2(data: &Self::Parsed) -> String {
+    fn part2(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
         let sum = data.iter().fold(0, |acc, &(start, end)| {
             let ids: Vec<u64> = calc_all(start, end);
             println!(
@@ -55,21 +57,7 @@ impl AoCDay for Day02 {
     }
 }
 
-fn main() {
-    // Allow: `cargo run --bin day02` or specify an input: `cargo run --bin day02 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day02.txt");
-
-    let raw = read_input(input_path);
-    let parsed = Day02::parse(&raw);
-
-    println!("🎄 Day 02 — Advent of Code 2025");
-    println!("⭐ Part 1: {}", Day02::part1(&parsed));
-    println!("⭐ Part 2: {}", Day02::part2(&parsed));
-}
+aoc_main!(Day02);
 
 fn calc_twice(start: u64, end: u64) -> Vec<u64> {
     calc(start, end, 2)
@@ -96,8 +84,7 @@ fn calc(start: u64, end: u64, divider: usize) -> Vec<u64> {
 
         if divider == 1 {
             let first = s.chars().next().unwrap();
-            let cand: u64 = std::iter::repeat(first)
-                .take(len)
+            let cand: u64 = std::iter::repeat_n(first, len)
                 .collect::<String>()
                 .parse()
                 .unwrap();
@@ -111,7 +98,7 @@ fn calc(start: u64, end: u64, divider: usize) -> Vec<u64> {
             continue;
         }
 
-        if len % divider == 0 {
+        if len.is_multiple_of(divider) {
             let chunk_len = len / divider;
             let first_chunk = &s[..chunk_len];
             let cand: u64 = first_chunk.repeat(divider).parse().unwrap();
@@ -147,17 +134,7 @@ mod tests {
         assert_eq!(parsed, vec![(30, 31), (22, 25)]);
     }
 
-    #[test]
-    fn test_part1() {
-        let parsed = Day02::parse(SAMPLE);
-        assert_eq!(Day02::part1(&parsed), "1227775554");
-    }
-
-    #[test]
-    fn test_part2() {
-        let parsed = Day02::parse(SAMPLE);
-        assert_eq!(Day02::part2(&parsed), "4174379265");
-    }
+    aoc_tests!(Day02, [(SAMPLE, "1227775554", "4174379265")]);
 
     #[test]
     fn test_calc() {
@@ -5,31 +5,65 @@
 
 use aoc2025::*;
 use std::ops::Not;
-use std::{env, usize};
 
 struct Day03;
 
+/// How many batteries to turn on per bank in each part.
+struct Params {
+    part1_batteries: u8,
+    part2_batteries: u8,
+}
+
+impl DayParams for Params {
+    fn puzzle() -> Self {
+        Self {
+            part1_batteries: 2,
+            part2_batteries: 12,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "part1_batteries" => self.part1_batteries = parse_batteries(key, value)?,
+            "part2_batteries" => self.part2_batteries = parse_batteries(key, value)?,
+            _ => return Err(format!("unknown parameter: {key}")),
+        }
+        Ok(())
+    }
+}
+
+/// A joltage of more than 19 digits would overflow `u64`.
+const MAX_BATTERIES: u8 = 19;
+
+fn parse_batteries(key: &str, value: &str) -> Result<u8, String> {
+    let count = parse_param(key, value)?;
+    if !(1..=MAX_BATTERIES).contains(&count) {
+        return Err(format!(
+            "{key} must be between 1 and {MAX_BATTERIES}, got {count}"
+        ));
+    }
+    Ok(count)
+}
+
 impl AoCDay for Day03 {
-    type Parsed = Vec<Vec<u8>>;
+    const DAY: u8 = 3;
 
-    fn parse(input: &str) -> Self::Parsed {
+    type Parsed<'a> = Vec<Vec<u8>>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
         input
             .lines()
-            .map(|line| {
-                line.trim()
-                    .chars()
-                    .map(|c| c.to_digit(10).unwrap() as u8)
-                    .collect::<Vec<_>>()
-            })
+            .map(parse_bank)
             .filter(|line| line.is_empty().not())
             .collect()
     }
 
-    fn part1(data: &Self::Parsed) -> String {
+    fn part1(data: &Self::Parsed<'_>, params: &Self::Params) -> String {
         let joltage = data
             .iter()
             .map(|line| {
-                let batteries = calc_joltage(line, 2);
+                let batteries = calc_joltage(line, params.part1_batteries);
                 println!("{}", batteries);
                 batteries
             })
@@ -37,11 +71,11 @@ impl AoCDay for Day03 {
         format!("{}", joltage)
     }
 
-    fn part2(data: &Self::Parsed) -> String {
+    fn part2(data: &Self::Parsed<'_>, params: &Self::Params) -> String {
         let joltage = data
             .iter()
             .map(|line| {
-                let batteries = calc_joltage(line, 12);
+                let batteries = calc_joltage(line, params.part2_batteries);
                 println!("{}", batteries);
                 batteries
             })
@@ -50,23 +84,41 @@ impl AoCDay for Day03 {
     }
 }
 
-fn main() {
-    // Allow: `cargo run --bin day03` or specify an input: `cargo run --bin day03 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day03.txt");
+aoc_main!(Day03, stream);
 
-    let raw = read_input(input_path);
-    let parsed = Day03::parse(&raw);
+impl StreamingDay for Day03 {
+    type Acc = (u64, u64);
 
-    println!("🎄 Day 03 — Advent of Code 2035");
-    println!("⭐ Part 1: {}", Day03::part1(&parsed));
-    println!("⭐ Part 2: {}", Day03::part2(&parsed));
+    fn init(_params: &Self::Params) -> Self::Acc {
+        (0, 0)
+    }
+
+    fn fold_line(acc: Self::Acc, line: &str, params: &Self::Params) -> Self::Acc {
+        let bank = parse_bank(line);
+        (
+            acc.0 + calc_joltage(&bank, params.part1_batteries),
+            acc.1 + calc_joltage(&bank, params.part2_batteries),
+        )
+    }
+
+    fn finish(acc: Self::Acc, _params: &Self::Params) -> (String, String) {
+        (acc.0.to_string(), acc.1.to_string())
+    }
+}
+
+fn parse_bank(line: &str) -> Vec<u8> {
+    line.trim()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as u8)
+        .collect()
 }
 
 fn calc_joltage(batteries: &[u8], length: u8) -> u64 {
+    assert!(
+        usize::from(length) <= batteries.len(),
+        "cannot turn on {length} batteries in a bank of {}",
+        batteries.len()
+    );
     (0..length)
         .rev()
         .fold((0usize, 0u64), |(start_idx, sum), i| {
@@ -110,40 +162,52 @@ mod tests {
         );
     }
 
+    aoc_tests!(Day03, [(SAMPLE, "357", "3121910778619")]);
+
     #[test]
-    fn test_part1() {
-        let parsed = Day03::parse(SAMPLE);
-        assert_eq!(Day03::part1(&parsed), "357");
+    fn test_stream() {
+        let answers = solve_stream::<Day03>(SAMPLE.as_bytes(), &Params::example()).unwrap();
+        assert_eq!(answers, ("357".to_string(), "3121910778619".to_string()));
     }
 
     #[test]
-    fn test_part2() {
-        let parsed = Day03::parse(SAMPLE);
-        assert_eq!(Day03::part2(&parsed), "3121910778619");
+    fn test_battery_params() {
+        let mut params = Params::example();
+        params.set("part1_batteries", "19").unwrap();
+        assert_eq!(params.part1_batteries, 19);
+        assert!(params.set("part1_batteries", "0").is_err());
+        assert!(params.set("part2_batteries", "20").is_err());
+        assert_eq!(params.part2_batteries, 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot turn on 4 batteries in a bank of 3")]
+    fn test_bank_too_short() {
+        calc_joltage(&[1, 2, 3], 4);
     }
 
     #[test]
     fn test_calc_joltage2() {
-        assert_eq!(calc_joltage(&vec![1, 1, 9], 2), 19);
-        assert_eq!(calc_joltage(&vec![1, 1, 1], 2), 11);
-        assert_eq!(calc_joltage(&vec![1, 9, 1], 2), 91);
-        assert_eq!(calc_joltage(&vec![6, 6, 1], 2), 66);
-        assert_eq!(calc_joltage(&vec![8, 7, 6, 7, 5, 4, 3, 2, 1], 2), 87);
-        assert_eq!(calc_joltage(&vec![8, 7, 6, 7, 5, 4, 3, 2, 1], 3), 877);
+        assert_eq!(calc_joltage(&[1, 1, 9], 2), 19);
+        assert_eq!(calc_joltage(&[1, 1, 1], 2), 11);
+        assert_eq!(calc_joltage(&[1, 9, 1], 2), 91);
+        assert_eq!(calc_joltage(&[6, 6, 1], 2), 66);
+        assert_eq!(calc_joltage(&[8, 7, 6, 7, 5, 4, 3, 2, 1], 2), 87);
+        assert_eq!(calc_joltage(&[8, 7, 6, 7, 5, 4, 3, 2, 1], 3), 877);
         assert_eq!(
-            calc_joltage(&vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1], 12),
+            calc_joltage(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1], 12),
             987654321111
         );
         assert_eq!(
-            calc_joltage(&vec![8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9], 12),
+            calc_joltage(&[8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9], 12),
             811111111119
         );
         assert_eq!(
-            calc_joltage(&vec![2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8], 12),
+            calc_joltage(&[2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8], 12),
             434234234278
         );
         assert_eq!(
-            calc_joltage(&vec![8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1], 12),
+            calc_joltage(&[8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1], 12),
             888911112111
         );
     }
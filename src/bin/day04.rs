@@ -3,102 +3,105 @@
 //! Replace the puzzle logic below with your real solution.
 //! The structure is: read → parse → solve part 1 & part 2.
 
-use aoc2025::grid::{Cell, Grid};
+use aoc2025::grid::{Automaton, BitGrid, Cell, Connectivity, Grid};
+use aoc2025::image::{Format, FrameRecorder, Rgb};
 use aoc2025::*;
-use std::env;
 
 struct Day04;
 
-impl AoCDay for Day04 {
-    type Parsed = Grid<bool>;
-
-    fn parse(input: &str) -> Self::Parsed {
-        let rows = input
-            .lines()
-            .map(|l| l.trim())
-            .filter(|l| !l.is_empty())
-            .map(|line| line.chars().map(|c| c == '@').collect())
-            .collect();
-        Grid::from_rows(rows)
-    }
+#[derive(Debug, Default)]
+struct Params {
+    /// Directory to write one PPM frame per removal round into.
+    frames: Option<String>,
+}
 
-    fn part1(data: &Self::Parsed) -> String {
-        let forklifts = Day04::forklifts(&data).count();
-        forklifts.to_string()
+impl DayParams for Params {
+    fn puzzle() -> Self {
+        Self::default()
     }
 
-    fn part2(data: &Self::Parsed) -> String {
-        let mut grid = data.clone();
-        let mut all_forklifts = 0;
-        loop {
-            let removed = Day04::remove_round(&mut grid);
-            println!("Removed {} rolls of paper.", removed);
-            all_forklifts += removed;
-            if removed == 0 {
-                break;
-            }
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "frames" => self.frames = Some(value.to_string()),
+            _ => return Err(format!("unknown parameter: {key}")),
         }
-        all_forklifts.to_string()
+        Ok(())
     }
 }
 
-impl Day04 {
-    fn is_forklift(cell: &Cell<bool>) -> bool {
-        let neighbors = cell.neighbors_8().filter(|c| *c.value).count();
-        *cell.value && neighbors < 4
+impl AoCDay for Day04 {
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = Grid<bool>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        Grid::parse(input, |c| match c {
+            '@' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("expected '@' or '.'"),
+        })
+        .expect("invalid paper roll grid")
     }
 
-    fn forklifts<'a>(grid: &'a Grid<bool>) -> impl Iterator<Item = (usize, usize)> + 'a {
-        grid.all()
-            .map(|cell| (cell, Self::is_forklift(&cell)))
-            .inspect(Self::print_cell(grid))
-            .filter(|(_, ok)| *ok)
-            .map(|(cell, _)| (cell.x, cell.y))
+    fn part1(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
+        let rolls = BitGrid::from(data);
+        let forklifts: Vec<(usize, usize)> =
+            (&rolls & &rolls.neighbor_count_below(4)).ones().collect();
+        Self::print_round(data, &forklifts);
+        forklifts.len().to_string()
     }
 
-    fn remove_round(grid: &mut Grid<bool>) -> usize {
-        let positions: Vec<(usize, usize)> = Self::forklifts(grid).collect();
+    fn part2(data: &Self::Parsed<'_>, params: &Self::Params) -> String {
+        let mut rolls = Automaton::new(data.clone(), |cell: Cell<'_, bool>| {
+            *cell.value && !Day04::is_forklift(&cell)
+        })
+        .incremental(Connectivity::Eight);
 
-        for (x, y) in &positions {
-            grid[(*x, *y)] = false;
+        let mut frames = FrameRecorder::new(4, Day04::colour);
+        loop {
+            if params.frames.is_some() {
+                frames.record(rolls.grid());
+            }
+            if rolls.step() == 0 {
+                break;
+            }
+        }
+        if let Some(dir) = &params.frames {
+            frames
+                .write_sequence(dir, Format::Ppm)
+                .expect("failed to write frames");
         }
 
-        positions.len()
-    }
+        rolls
+            .changes()
+            .iter()
+            .for_each(|removed| println!("Removed {} rolls of paper.", removed));
+        print!("{}", rolls.grid().map(|&roll| if roll { '@' } else { '.' }));
 
-    fn print_cell<'a>(grid: &'a Grid<bool>) -> impl Fn(&(Cell<'a, bool>, bool)) + 'a {
-        move |(cell, ok)| {
-            print!(
-                "{}",
-                match (*ok, *cell.value) {
-                    (true, _) => 'x',
-                    (false, true) => '@',
-                    (false, false) => '.',
-                }
-            );
-            if cell.x == grid.width() - 1 {
-                println!();
-            }
-        }
+        rolls.changes().iter().sum::<usize>().to_string()
     }
 }
 
-fn main() {
-    // Allow: `cargo run --bin day04` or specify an input: `cargo run --bin day04 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day04.txt");
+impl Day04 {
+    fn colour(&roll: &bool) -> Rgb {
+        if roll { [139, 90, 43] } else { [245, 245, 220] }
+    }
 
-    let raw = read_input(input_path);
-    let parsed = Day04::parse(&raw);
+    fn is_forklift(cell: &Cell<bool>) -> bool {
+        let neighbors = cell.neighbors_8().filter(|c| *c.value).count();
+        *cell.value && neighbors < 4
+    }
 
-    println!("🎄 Day 04 — Advent of Code 2045");
-    println!("⭐ Part 1: {}", Day04::part1(&parsed));
-    println!("⭐ Part 2: {}", Day04::part2(&parsed));
+    fn print_round(grid: &Grid<bool>, removable: &[(usize, usize)]) {
+        let mut marked = grid.map(|&roll| if roll { '@' } else { '.' });
+        removable.iter().for_each(|&pos| marked[pos] = 'x');
+        print!("{marked}");
+    }
 }
 
+aoc_main!(Day04);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,8 +122,8 @@ mod tests {
     #[test]
     fn test_parse() {
         let parsed = Day04::parse(SAMPLE);
-        assert_eq!(parsed.get(0, 0), Some(&false),);
-        assert_eq!(parsed.get(1, 1), Some(&true),);
+        assert_eq!(parsed.get((0, 0)), Some(&false),);
+        assert_eq!(parsed.get((1, 1)), Some(&true),);
         assert_eq!(
             parsed
                 .neighbors_8(0, 0)
@@ -137,15 +140,5 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part1() {
-        let parsed = Day04::parse(SAMPLE);
-        assert_eq!(Day04::part1(&parsed), "13");
-    }
-
-    #[test]
-    fn test_part2() {
-        let parsed = Day04::parse(SAMPLE);
-        assert_eq!(Day04::part2(&parsed), "43");
-    }
+    aoc_tests!(Day04, [(SAMPLE, "13", "43")]);
 }
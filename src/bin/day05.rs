@@ -5,15 +5,17 @@
 
 use aoc2025::*;
 use itertools::Itertools;
-use std::env;
 use std::ops::RangeInclusive;
 
 struct Day05;
 
 impl AoCDay for Day05 {
-    type Parsed = (Vec<RangeInclusive<u64>>, Vec<u64>);
+    const DAY: u8 = 5;
 
-    fn parse(input: &str) -> Self::Parsed {
+    type Parsed<'a> = (Vec<RangeInclusive<u64>>, Vec<u64>);
+    type Params = ();
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
         let (ranges_block, ids_block) = input
             .split_once("\n\n")
             .expect("input must contain a blank line between ranges and ids");
@@ -42,27 +44,37 @@ impl AoCDay for Day05 {
         (ranges, ids)
     }
 
-    fn part1(data: &Self::Parsed) -> String {
-        let (ranges, ids) = data.clone();
-        let spoiled = ids
-            .iter()
-            .filter(|id| ranges.iter().any(|r| r.contains(&id)))
-            .count();
+    fn part1(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
+        let (ranges, ids) = data;
+        Self::count_fresh(ranges, ids).to_string()
+    }
 
-        spoiled.to_string()
+    fn part2(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
+        let (ranges, _) = data;
+        Self::total_fresh(&Self::merge_all(ranges.clone())).to_string()
     }
 
-    fn part2(data: &Self::Parsed) -> String {
-        let (ranges, _) = data.clone();
-        let fresh_ingredients: u64 = Self::merge_all(ranges)
-            .iter()
-            .map(|r| r.end() - r.start() + 1)
-            .sum();
-        fresh_ingredients.to_string()
+    fn solve(data: &Self::Parsed<'_>, _params: &Self::Params) -> (String, String) {
+        let (ranges, ids) = data;
+        let merged = Self::merge_all(ranges.clone());
+        (
+            Self::count_fresh(&merged, ids).to_string(),
+            Self::total_fresh(&merged).to_string(),
+        )
     }
 }
 
 impl Day05 {
+    fn count_fresh(ranges: &[RangeInclusive<u64>], ids: &[u64]) -> usize {
+        ids.iter()
+            .filter(|id| ranges.iter().any(|r| r.contains(id)))
+            .count()
+    }
+
+    fn total_fresh(merged: &[RangeInclusive<u64>]) -> u64 {
+        merged.iter().map(|r| r.end() - r.start() + 1).sum()
+    }
+
     fn merge_all(ranges: Vec<RangeInclusive<u64>>) -> Vec<RangeInclusive<u64>> {
         let mut iter = ranges.into_iter();
         let first = iter.next().unwrap();
@@ -82,21 +94,7 @@ impl Day05 {
     }
 }
 
-fn main() {
-    // Allow: `cargo run --bin day05` or specify an input: `cargo run --bin day05 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day05.txt");
-
-    let raw = read_input(input_path);
-    let parsed = Day05::parse(&raw);
-
-    println!("🎄 Day 05 — Advent of Code 2025");
-    println!("⭐ Part 1: {}", Day05::part1(&parsed));
-    println!("⭐ Part 2: {}", Day05::part2(&parsed));
-}
+aoc_main!(Day05);
 
 #[cfg(test)]
 mod tests {
@@ -133,17 +131,7 @@ mod tests {
         assert_eq!(ids, vec![1, 5, 8, 11, 17, 32]);
     }
 
-    #[test]
-    fn test_part1() {
-        let parsed = Day05::parse(SAMPLE);
-        assert_eq!(Day05::part1(&parsed), "3");
-    }
-
-    #[test]
-    fn test_part2() {
-        let parsed = Day05::parse(SAMPLE);
-        assert_eq!(Day05::part2(&parsed), "14");
-    }
+    aoc_tests!(Day05, [(SAMPLE, "3", "14")]);
 
     #[test]
     fn single_range_is_unchanged() {
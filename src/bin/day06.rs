@@ -3,15 +3,19 @@
 //! Replace the puzzle logic below with your real solution.
 //! The structure is: read → parse → solve part 1 & part 2.
 
+use aoc2025::grid::Grid;
 use aoc2025::*;
-use std::env;
 
 struct Day06;
 
 impl AoCDay for Day06 {
-    type Parsed = Vec<(Vec<u64>, char)>;
+    const DAY: u8 = 6;
 
-    fn parse(input: &str) -> Self::Parsed {
+    /// The row-wise problems, plus the raw worksheet for the column-wise reading.
+    type Parsed<'a> = (Vec<(Vec<u64>, char)>, &'a str);
+    type Params = ();
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
         let mut lines: Vec<_> = input.lines().filter(|l| !l.trim().is_empty()).collect();
         let ops: Vec<char> = lines
             .pop()
@@ -24,13 +28,16 @@ impl AoCDay for Day06 {
             .map(|l| l.split_whitespace().map(|n| n.parse().unwrap()).collect())
             .collect();
 
-        (0..ops.len())
+        let problems = (0..ops.len())
             .map(|c| (rows.iter().map(|r| r[c]).collect(), ops[c]))
-            .collect()
+            .collect();
+
+        (problems, input)
     }
 
-    fn part1(data: &Self::Parsed) -> String {
-        let sum: u64 = data
+    fn part1(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
+        let (problems, _) = data;
+        let sum: u64 = problems
             .iter()
             .map(|(nums, op)| match op {
                 '+' => nums.iter().copied().sum::<u64>(),
@@ -41,48 +48,37 @@ impl AoCDay for Day06 {
         sum.to_string()
     }
 
-    fn part2(data: &Self::Parsed) -> String {
-        String::new()
+    fn part2(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
+        let (_, worksheet) = data;
+        Self::solve_cephalopod(worksheet).to_string()
     }
 }
 
 impl Day06 {
     pub fn solve_cephalopod(input: &str) -> u64 {
-        let lines: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
+        let lines: Vec<&[u8]> = input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.as_bytes())
+            .collect();
         let h = lines.len();
         if h == 0 {
             return 0;
         }
 
         let w = lines.iter().map(|l| l.len()).max().unwrap_or(0);
-        let op_row = h - 1;
+        let sheet = Grid::from_fn(w, h, |(x, y)| lines[y].get(x).copied().unwrap_or(b' '));
+        let digits = sheet.view(0..w, 0..h - 1);
+        let ops = sheet.row(h - 1);
 
         let mut total = 0u64;
         let mut nums: Vec<u64> = Vec::new();
         let mut op: Option<u8> = None;
 
-        for col in 0..w {
-            let mut non_space = false;
-            let mut n = 0u64;
-            let mut has_digit = false;
-            let mut col_op: Option<u8> = None;
-
-            for (row, line) in lines.iter().enumerate() {
-                let c = line.get(col).copied().unwrap_or(b' ');
-                if c == b' ' {
-                    continue;
-                }
-                non_space = true;
-
-                if row == op_row {
-                    col_op = Some(c);
-                } else if c.is_ascii_digit() {
-                    n = n * 10 + (c - b'0') as u64;
-                    has_digit = true;
-                }
-            }
+        for (x, &col_op) in ops.iter().enumerate() {
+            let column: Vec<u8> = digits.col(x).copied().filter(u8::is_ascii_digit).collect();
 
-            if !non_space {
+            if column.is_empty() && col_op == b' ' {
                 if !nums.is_empty() || op.is_some() {
                     total += Self::eval_problem_stream(&nums, op);
                     nums.clear();
@@ -91,11 +87,11 @@ impl Day06 {
                 continue;
             }
 
-            if has_digit {
-                nums.push(n);
+            if !column.is_empty() {
+                nums.push(column.iter().fold(0, |n, &d| n * 10 + (d - b'0') as u64));
             }
-            if let Some(o) = col_op {
-                op.get_or_insert(o);
+            if col_op != b' ' {
+                op.get_or_insert(col_op);
             }
         }
 
@@ -111,27 +107,13 @@ impl Day06 {
 
         match op {
             b'+' => nums.iter().copied().sum(),
-            b'*' => nums.iter().copied().fold(1, |a, n| a * n),
+            b'*' => nums.iter().copied().product(),
             _ => panic!("Unknown operator"),
         }
     }
 }
 
-fn main() {
-    // Allow: `cargo run --bin day06` or specify an input: `cargo run --bin day06 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day06.txt");
-
-    let raw = read_input(input_path);
-    let parsed = Day06::parse(&raw);
-
-    println!("🎄 Day 06 — Advent of Code 2025");
-    println!("⭐ Part 1: {}", Day06::part1(&parsed));
-    println!("⭐ Part 2: {}", Day06::solve_cephalopod(&raw));
-}
+aoc_main!(Day06);
 
 #[cfg(test)]
 mod tests {
@@ -145,7 +127,7 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let columns = Day06::parse(SAMPLE);
+        let (columns, _) = Day06::parse(SAMPLE);
         let nums: Vec<Vec<u64>> = columns.iter().map(|(nums, _)| nums.clone()).collect();
 
         assert_eq!(
@@ -163,14 +145,5 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part1() {
-        let parsed = Day06::parse(SAMPLE);
-        assert_eq!(Day06::part1(&parsed), "4277556");
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(Day06::solve_cephalopod(SAMPLE).to_string(), "3263827");
-    }
+    aoc_tests!(Day06, [(SAMPLE, "4277556", "3263827")]);
 }
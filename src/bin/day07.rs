@@ -3,106 +3,90 @@
 //! Replace the puzzle logic below with your real solution.
 //! The structure is: read → parse → solve part 1 & part 2.
 
+use std::collections::{HashMap, HashSet};
+
+use aoc2025::grid::Grid;
+use aoc2025::point::{Direction, Point};
 use aoc2025::*;
-use itertools::Itertools;
-use std::env;
 
 struct Day07;
 
 impl AoCDay for Day07 {
-    type Parsed = (usize, Vec<Vec<usize>>);
-
-    fn parse(input: &str) -> Self::Parsed {
-        let lines: Vec<&str> = input.lines().collect();
-        let width = lines.first().map(|l| l.chars().count()).unwrap_or(0);
-
-        let rows = lines
-            .iter()
-            .map(|l| {
-                l.chars()
-                    .enumerate()
-                    .filter(|(_, c)| *c != '.')
-                    .map(|(i, _)| i)
-                    .collect_vec()
-            })
-            .collect_vec();
-
-        (width, rows)
+    const DAY: u8 = 7;
+
+    type Parsed<'a> = Grid<char>;
+    type Params = ();
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        Grid::parse(input, |c| match c {
+            '.' | 'S' | '^' => Ok(c),
+            _ => Err("expected '.', 'S' or '^'"),
+        })
+        .expect("invalid tachyon manifold")
     }
 
-    fn part1(data: &Self::Parsed) -> String {
-        let (_, lines) = data;
-        let (start, tree) = lines.split_first().unwrap();
-        let (_, splits) = tree
-            .iter()
-            .fold((start.clone(), 0usize), |(beams, splits), row| {
-                let mut new_splits = splits;
-
-                let mut new_beams = beams
-                    .into_iter()
-                    .flat_map(|beam| {
-                        if row.contains(&beam) {
-                            new_splits += 1;
-                            [beam - 1, beam + 1].into_iter().collect_vec()
-                        } else {
-                            [beam].into_iter().collect_vec()
-                        }
-                    })
-                    .collect_vec();
-
-                new_beams.sort_unstable();
-                new_beams.dedup();
-
-                (new_beams, new_splits)
-            });
-
-        splits.to_string()
+    fn part1(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
+        let mut split = HashSet::new();
+        let mut beams = vec![Self::start(data)];
+
+        while let Some(beam) = beams.pop() {
+            let Some((splitter, _)) = Self::next_splitter(data, beam) else {
+                continue;
+            };
+            if split.insert(splitter) {
+                beams.extend(Self::split(data, splitter));
+            }
+        }
+
+        split.len().to_string()
     }
 
-    fn part2(data: &Self::Parsed) -> String {
-        let (width, rows) = data;
-        let (start_row, tree) = rows.split_first().unwrap();
-
-        let start_x = *start_row.first().expect("start row must contain S");
-
-        let init_count = vec![1_u64; *width];
-
-        let final_count = tree.iter().rev().fold(init_count, |count, row| {
-            (0..*width)
-                .map(|i| {
-                    if row.contains(&i) {
-                        let left  = count.get(i.wrapping_sub(1)).copied().unwrap_or(0);
-                        let right = count.get(i + 1).copied().unwrap_or(0);
-                        left + right
-                    } else {
-                        count[i]
-                    }
-                })
-                .collect_vec()
-        });
-
-        final_count[start_x].to_string()
+    fn part2(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
+        let mut memo = HashMap::new();
+        Self::timelines(data, Self::start(data), &mut memo).to_string()
     }
 }
 
-impl Day07 {}
+impl Day07 {
+    fn start(grid: &Grid<char>) -> Point {
+        let (x, y) = grid
+            .coords()
+            .find(|&p| grid[p] == 'S')
+            .expect("manifold must contain S");
+        Point::from((x, y))
+    }
 
-fn main() {
-    // Allow: `cargo run --bin day07` or specify an input: `cargo run --bin day07 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day07.txt");
+    /// The splitter a beam entering below `beam` runs into, if any.
+    fn next_splitter(grid: &Grid<char>, beam: Point) -> Option<(Point, &char)> {
+        grid.first_hit(beam, Direction::South, |&c| c == '^')
+    }
 
-    let raw = read_input(input_path);
-    let parsed = Day07::parse(&raw);
+    /// The beams leaving a splitter to its left and right.
+    fn split(grid: &Grid<char>, splitter: Point) -> impl Iterator<Item = Point> + '_ {
+        [Direction::West, Direction::East]
+            .into_iter()
+            .filter_map(move |dir| grid.step(splitter, dir))
+    }
 
-    println!("🎄 Day 07 — Advent of Code 2025");
-    println!("⭐ Part 1: {}", Day07::part1(&parsed));
-    println!("⭐ Part 2: {}", Day07::part2(&parsed));
+    /// Number of timelines a particle starting at `beam` ends up in.
+    fn timelines(grid: &Grid<char>, beam: Point, memo: &mut HashMap<Point, u64>) -> u64 {
+        let Some((splitter, _)) = Self::next_splitter(grid, beam) else {
+            return 1;
+        };
+        if let Some(&count) = memo.get(&splitter) {
+            return count;
+        }
+
+        let count = Self::split(grid, splitter)
+            .map(|next| Self::timelines(grid, next, memo))
+            .sum();
+        memo.insert(splitter, count);
+        count
+    }
 }
 
+aoc_main!(Day07);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,40 +110,15 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let (width, rows) = Day07::parse(SAMPLE);
-        assert_eq!(width, 15);
+        let grid = Day07::parse(SAMPLE);
+        assert_eq!((grid.width(), grid.height()), (15, 16));
+        assert_eq!(Day07::start(&grid), Point::new(7, 0));
+        assert_eq!(grid.row(4).iter().filter(|&&c| c == '^').count(), 2);
         assert_eq!(
-            rows,
-            vec![
-                vec![7],
-                vec![],
-                vec![7],
-                vec![],
-                vec![6, 8],
-                vec![],
-                vec![5, 7, 9],
-                vec![],
-                vec![4, 6, 10],
-                vec![],
-                vec![3, 5, 9, 11],
-                vec![],
-                vec![2, 6, 12],
-                vec![],
-                vec![1, 3, 5, 7, 9, 13],
-                vec![]
-            ]
+            Day07::next_splitter(&grid, Day07::start(&grid)),
+            Some((Point::new(7, 2), &'^'))
         );
     }
 
-    #[test]
-    fn test_part1() {
-        let parsed = Day07::parse(SAMPLE);
-        assert_eq!(Day07::part1(&parsed), "21");
-    }
-
-    #[test]
-    fn test_part2() {
-        let parsed = Day07::parse(SAMPLE);
-        assert_eq!(Day07::part2(&parsed), "40");
-    }
+    aoc_tests!(Day07, [(SAMPLE, "21", "40")]);
 }
@@ -3,56 +3,31 @@
 //! Replace the puzzle logic below with your real solution.
 //! The structure is: read → parse → solve part 1 & part 2.
 
+use aoc2025::search::bfs;
 use aoc2025::*;
 use good_lp::{highs, variable, variables, Expression, Solution, SolverModel};
 use itertools::Itertools;
-use std::collections::{HashSet, VecDeque};
-use std::env;
 
 struct Day10;
 
+/// Indicator light diagram, button wiring and joltage requirements.
+type Machine = (Vec<usize>, Vec<Vec<usize>>, Vec<usize>);
+
 impl AoCDay for Day10 {
-    type Parsed = Vec<(Vec<usize>, Vec<Vec<usize>>, Vec<usize>)>;
+    const DAY: u8 = 10;
+
+    type Parsed<'a> = Vec<Machine>;
+    type Params = ();
 
-    fn parse(input: &str) -> Self::Parsed {
+    fn parse(input: &str) -> Self::Parsed<'_> {
         input
             .lines()
             .filter(|line| !line.trim().is_empty())
-            .map(|line| {
-                let tokens = line.split_whitespace().collect_vec();
-
-                let (diagram, rest) = tokens.split_first().unwrap();
-                let (requirements, wiring_tokens) = rest.split_last().unwrap();
-
-                let diagram_indices = diagram
-                    .chars()
-                    .dropping(1)
-                    .dropping_back(1)
-                    .enumerate()
-                    .filter_map(|(i, c)| (c == '#').then_some(i))
-                    .collect_vec();
-
-                let wiring = wiring_tokens
-                    .iter()
-                    .map(|s| {
-                        s[1..s.len() - 1]
-                            .split(',')
-                            .map(|n| n.parse::<usize>().unwrap())
-                            .collect_vec()
-                    })
-                    .collect_vec();
-
-                let requirements_vec = requirements[1..requirements.len() - 1]
-                    .split(',')
-                    .map(|n| n.parse::<usize>().unwrap())
-                    .collect_vec();
-
-                (diagram_indices, wiring, requirements_vec)
-            })
+            .map(Day10::parse_machine)
             .collect_vec()
     }
 
-    fn part1(data: &Self::Parsed) -> String {
+    fn part1(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
         let sum = data
             .iter()
             .map(|(indices, wiring, _)| Day10::calc_min_clicks(indices, wiring))
@@ -60,7 +35,7 @@ impl AoCDay for Day10 {
         sum.to_string()
     }
 
-    fn part2(data: &Self::Parsed) -> String {
+    fn part2(data: &Self::Parsed<'_>, _params: &Self::Params) -> String {
         let sum = data
             .iter()
             .map(|(_, wiring, requirements)| Day10::calc_min_joltage(requirements, wiring))
@@ -69,7 +44,59 @@ impl AoCDay for Day10 {
     }
 }
 
+impl StreamingDay for Day10 {
+    type Acc = (u64, u64);
+
+    fn init(_params: &Self::Params) -> Self::Acc {
+        (0, 0)
+    }
+
+    fn fold_line(acc: Self::Acc, line: &str, _params: &Self::Params) -> Self::Acc {
+        let (indices, wiring, requirements) = Day10::parse_machine(line);
+        (
+            acc.0 + Day10::calc_min_clicks(&indices, &wiring),
+            acc.1 + Day10::calc_min_joltage(&requirements, &wiring),
+        )
+    }
+
+    fn finish(acc: Self::Acc, _params: &Self::Params) -> (String, String) {
+        (acc.0.to_string(), acc.1.to_string())
+    }
+}
+
 impl Day10 {
+    fn parse_machine(line: &str) -> Machine {
+        let tokens = line.split_whitespace().collect_vec();
+
+        let (diagram, rest) = tokens.split_first().unwrap();
+        let (requirements, wiring_tokens) = rest.split_last().unwrap();
+
+        let diagram_indices = diagram
+            .chars()
+            .dropping(1)
+            .dropping_back(1)
+            .enumerate()
+            .filter_map(|(i, c)| (c == '#').then_some(i))
+            .collect_vec();
+
+        let wiring = wiring_tokens
+            .iter()
+            .map(|s| {
+                s[1..s.len() - 1]
+                    .split(',')
+                    .map(|n| n.parse::<usize>().unwrap())
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let requirements_vec = requirements[1..requirements.len() - 1]
+            .split(',')
+            .map(|n| n.parse::<usize>().unwrap())
+            .collect_vec();
+
+        (diagram_indices, wiring, requirements_vec)
+    }
+
     fn calc_min_clicks(diagram: &[usize], wiring: &[Vec<usize>]) -> u64 {
         if diagram.is_empty() {
             return 0;
@@ -82,32 +109,13 @@ impl Day10 {
             .map(|btn| btn.iter().fold(0u64, |mask, &i| mask ^ (1u64 << i)))
             .collect();
 
-        let start: u64 = 0;
-        if start == target {
-            return 0;
-        }
-
-        let mut visited: HashSet<u64> = HashSet::new();
-        let mut queue: VecDeque<(u64, u64)> = VecDeque::new();
-
-        visited.insert(start);
-        queue.push_back((start, 0));
-
-        while let Some((state, dist)) = queue.pop_front() {
-            for &bmask in &button_masks {
-                let next = state ^ bmask;
-                if !visited.insert(next) {
-                    continue;
-                }
-                let next_dist = dist + 1;
-                if next == target {
-                    return next_dist;
-                }
-                queue.push_back((next, next_dist));
-            }
-        }
-
-        panic!("No solution found for given machine configuration");
+        bfs(
+            [0u64],
+            |&state| button_masks.iter().map(move |&bmask| state ^ bmask),
+            |&state| state == target,
+        )
+        .cost()
+        .expect("No solution found for given machine configuration") as u64
     }
 
     fn calc_min_joltage(requirements: &[usize], wiring: &[Vec<usize>]) -> u64 {
@@ -156,21 +164,7 @@ impl Day10 {
     }
 }
 
-fn main() {
-    // Allow: `cargo run --bin day10` or specify an input: `cargo run --bin day10 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day10.txt");
-
-    let raw = read_input(input_path);
-    let parsed = Day10::parse(&raw);
-
-    println!("🎄 Day 10 — Advent of Code 2025");
-    println!("⭐ Part 1: {}", Day10::part1(&parsed));
-    println!("⭐ Part 2: {}", Day10::part2(&parsed));
-}
+aoc_main!(Day10, stream);
 
 #[cfg(test)]
 mod tests {
@@ -203,15 +197,11 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part1() {
-        let parsed = Day10::parse(SAMPLE);
-        assert_eq!(Day10::part1(&parsed), "7");
-    }
+    aoc_tests!(Day10, [(SAMPLE, "7", "33")]);
 
     #[test]
-    fn test_part2() {
-        let parsed = Day10::parse(SAMPLE);
-        assert_eq!(Day10::part2(&parsed), "33");
+    fn test_stream() {
+        let answers = solve_stream::<Day10>(SAMPLE.as_bytes(), &()).unwrap();
+        assert_eq!(answers, ("7".to_string(), "33".to_string()));
     }
 }
@@ -4,39 +4,81 @@
 //! The structure is: read → parse → solve part 1 & part 2.
 
 use aoc2025::*;
+use itertools::Itertools;
 use std::collections::HashMap;
-use std::env;
 
 struct Day11;
 
+/// Where the paths start and end, and which nodes part 2 must pass through.
+struct Params {
+    part1_start: String,
+    part2_start: String,
+    target: String,
+    required: Vec<String>,
+}
+
+impl DayParams for Params {
+    fn puzzle() -> Self {
+        Self {
+            part1_start: "you".to_string(),
+            part2_start: "svr".to_string(),
+            target: "out".to_string(),
+            required: vec!["dac".to_string(), "fft".to_string()],
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "part1_start" => self.part1_start = value.to_string(),
+            "part2_start" => self.part2_start = value.to_string(),
+            "target" => self.target = value.to_string(),
+            "required" => {
+                let required: Vec<String> = value
+                    .split(',')
+                    .filter(|s| !s.is_empty())
+                    .unique()
+                    .map(str::to_string)
+                    .collect();
+                // Part 2 tracks the visited nodes as bits of a `u64` mask.
+                if required.len() > 63 {
+                    return Err(format!(
+                        "at most 63 required nodes are supported, got {}",
+                        required.len()
+                    ));
+                }
+                self.required = required;
+            }
+            _ => return Err(format!("unknown parameter: {key}")),
+        }
+        Ok(())
+    }
+}
+
 impl AoCDay for Day11 {
-    type Parsed = HashMap<String, Vec<String>>;
+    const DAY: u8 = 11;
+
+    type Parsed<'a> = HashMap<&'a str, Vec<&'a str>>;
+    type Params = Params;
 
-    fn parse(input: &str) -> Self::Parsed {
+    fn parse(input: &str) -> Self::Parsed<'_> {
         input
             .lines()
             .filter(|line| !line.trim().is_empty())
             .map(|line| {
                 let (key, value) = line.split_once(":").unwrap();
-                (
-                    key.trim().to_string(),
-                    value
-                        .split_whitespace()
-                        .map(|s| s.trim().to_string())
-                        .collect(),
-                )
+                (key.trim(), value.split_whitespace().collect())
             })
             .collect()
     }
 
-    fn part1(data: &Self::Parsed) -> String {
+    fn part1(data: &Self::Parsed<'_>, params: &Self::Params) -> String {
         let required_index: HashMap<&str, usize> = HashMap::new();
         let full_mask: u64 = 0;
 
         let mut memo = HashMap::new();
         let result = Day11::count_paths_with_requirements(
-            "you",
-            "out",
+            &params.part1_start,
+            &params.target,
             data,
             &required_index,
             0,
@@ -47,17 +89,20 @@ impl AoCDay for Day11 {
         result.to_string()
     }
 
-    fn part2(data: &Self::Parsed) -> String {
-        let mut required_index: HashMap<&str, usize> = HashMap::new();
-        required_index.insert("dac", 0);
-        required_index.insert("fft", 1);
+    fn part2(data: &Self::Parsed<'_>, params: &Self::Params) -> String {
+        let required_index: HashMap<&str, usize> = params
+            .required
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.as_str(), i))
+            .collect();
 
         let full_mask: u64 = (1u64 << required_index.len()) - 1;
 
         let mut memo = HashMap::new();
         let result = Day11::count_paths_with_requirements(
-            "svr",
-            "out",
+            &params.part2_start,
+            &params.target,
             data,
             &required_index,
             0,
@@ -73,7 +118,7 @@ impl Day11 {
     fn count_paths_with_requirements<'a>(
         node: &'a str,
         target: &'a str,
-        graph: &'a HashMap<String, Vec<String>>,
+        graph: &'a HashMap<&'a str, Vec<&'a str>>,
         required_index: &HashMap<&'a str, usize>,
         visited_mask: u64,
         full_mask: u64,
@@ -105,7 +150,7 @@ impl Day11 {
             .iter()
             .map(|child| {
                 Self::count_paths_with_requirements(
-                    child.as_str(),
+                    child,
                     target,
                     graph,
                     required_index,
@@ -121,21 +166,7 @@ impl Day11 {
     }
 }
 
-fn main() {
-    // Allow: `cargo run --bin day11` or specify an input: `cargo run --bin day11 -- foo.txt`
-    let args: Vec<String> = env::args().collect();
-    let input_path = args
-        .get(1)
-        .map(String::as_str)
-        .unwrap_or("inputs/day11.txt");
-
-    let raw = read_input(input_path);
-    let parsed = Day11::parse(&raw);
-
-    println!("🎄 Day 11 — Advent of Code 2025");
-    println!("⭐ Part 1: {}", Day11::part1(&parsed));
-    println!("⭐ Part 2: {}", Day11::part2(&parsed));
-}
+aoc_main!(Day11);
 
 #[cfg(test)]
 mod tests {
@@ -175,18 +206,23 @@ mod tests {
         let parsed = Day11::parse(SAMPLE1);
         assert_eq!(parsed.len(), 10);
         let value = parsed.get("you").unwrap();
-        assert_eq!(*value, vec!["bbb".to_string(), "ccc".to_string(),]);
+        assert_eq!(*value, vec!["bbb", "ccc"]);
     }
 
     #[test]
-    fn test_part1() {
-        let parsed = Day11::parse(SAMPLE1);
-        assert_eq!(Day11::part1(&parsed), "5");
-    }
-
-    #[test]
-    fn test_part2() {
+    fn test_required_param() {
+        let mut params = Params::example();
+        params.set("required", "dac,fft,dac").unwrap();
+        assert_eq!(params.required, ["dac", "fft"]);
         let parsed = Day11::parse(SAMPLE2);
-        assert_eq!(Day11::part2(&parsed), "2");
+        assert_eq!(Day11::part2(&parsed, &params), "2");
+
+        let many = (0..64).map(|i| format!("n{i}")).join(",");
+        assert!(params.set("required", &many).is_err());
     }
+
+    aoc_tests!(Day11, [
+        (SAMPLE1, "5", _),
+        (SAMPLE2, _, "2"),
+    ]);
 }
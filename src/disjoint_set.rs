@@ -0,0 +1,200 @@
+//! Union-find over the integers `0..n`, for puzzles that merge things into
+//! circuits, islands or clusters.
+
+/// A partition of `0..n` into disjoint sets, merged by size.
+///
+/// Sets made with [`DisjointSet::new`] compress paths on every lookup.
+/// [`DisjointSet::with_rollback`] keeps paths intact instead, so unions can
+/// be undone back to a [`Snapshot`].
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+    /// Roots absorbed by each union, if rollback is enabled.
+    history: Option<Vec<usize>>,
+}
+
+/// A point in a [`DisjointSet`]'s history to roll back to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot(usize);
+
+impl DisjointSet {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+            history: None,
+        }
+    }
+
+    /// `n` singleton sets whose unions can be undone with [`Self::rollback`].
+    pub fn with_rollback(n: usize) -> Self {
+        Self {
+            history: Some(Vec::new()),
+            ..Self::new(n)
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of `x`'s set.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        if self.history.is_some() {
+            return self.root(x);
+        }
+        // Path halving: point every other node at its grandparent.
+        while self.parent[x] != x {
+            let p = self.parent[x];
+            self.parent[x] = self.parent[p];
+            x = p;
+        }
+        x
+    }
+
+    /// The representative of `x`'s set, without compressing the path.
+    fn root(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Whether `a` and `b` are in the same set.
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Merges the sets of `a` and `b`. Returns `false` if they were already
+    /// one set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut ra, mut rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+
+        if self.size[ra] < self.size[rb] {
+            std::mem::swap(&mut ra, &mut rb);
+        }
+        self.parent[rb] = ra;
+        self.size[ra] += self.size[rb];
+        self.components -= 1;
+        if let Some(history) = &mut self.history {
+            history.push(rb);
+        }
+        true
+    }
+
+    /// Size of the set containing `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The representatives of all sets, in increasing order.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(|&x| self.parent[x] == x)
+    }
+
+    /// The size of every set, ordered by representative.
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.roots().map(|r| self.size[r])
+    }
+
+    /// The members of every set, each sorted, ordered by smallest member.
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+
+        for x in 0..self.len() {
+            let root = self.root(x);
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[index[root]].push(x);
+        }
+        groups
+    }
+
+    /// The current state, to pass to [`Self::rollback`] later.
+    ///
+    /// # Panics
+    ///
+    /// If the set was not made with [`Self::with_rollback`].
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.history().len())
+    }
+
+    /// Undoes every union made since `snapshot` was taken.
+    ///
+    /// # Panics
+    ///
+    /// If the set was not made with [`Self::with_rollback`].
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        while self.history().len() > snapshot.0 {
+            let child = self.history.as_mut().unwrap().pop().unwrap();
+            let root = self.parent[child];
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.components += 1;
+        }
+    }
+
+    fn history(&self) -> &[usize] {
+        self.history
+            .as_deref()
+            .expect("rollback needs DisjointSet::with_rollback")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn unions() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert_eq!(set.components(), 3);
+        assert_eq!(set.size_of(3), 4);
+        assert!(set.same(0, 3));
+        assert!(!set.same(0, 4));
+        assert_eq!(set.groups(), [vec![0, 1, 2, 3], vec![4], vec![5]]);
+        assert_eq!(set.sizes().sorted().rev().collect_vec(), [4, 1, 1]);
+    }
+
+    #[test]
+    fn rollback() {
+        let mut set = DisjointSet::with_rollback(4);
+        set.union(0, 1);
+        let before = set.snapshot();
+        set.union(2, 3);
+        set.union(1, 2);
+        assert_eq!(set.components(), 1);
+
+        set.rollback(before);
+        assert_eq!(set.components(), 3);
+        assert_eq!(set.groups(), [vec![0, 1], vec![2], vec![3]]);
+        assert_eq!(set.size_of(0), 2);
+    }
+}
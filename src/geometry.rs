@@ -0,0 +1,628 @@
+//! Exact geometry on integer coordinates: rectangles, rectilinear polygons
+//! and regions bounded by several of them.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::grid::{AreaSums, CompressedGrid};
+use crate::point::Point;
+
+/// A closed axis-aligned rectangle; `min` and `max` are both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The rectangle spanned by two opposite corners, in any order.
+    pub fn from_corners(a: Point, b: Point) -> Self {
+        Self {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    pub fn width(&self) -> u64 {
+        self.min.x.abs_diff(self.max.x)
+    }
+
+    pub fn height(&self) -> u64 {
+        self.min.y.abs_diff(self.max.y)
+    }
+
+    /// Geometric area; zero for a rectangle of zero width or height.
+    pub fn area(&self) -> u64 {
+        self.width() * self.height()
+    }
+
+    /// Number of integer points inside or on the edge, i.e. the tiles the
+    /// rectangle covers when every point is a tile.
+    pub fn lattice_points(&self) -> u64 {
+        (self.width() + 1) * (self.height() + 1)
+    }
+
+    /// Whether `p` lies inside or on the edge.
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Whether the two closed rectangles share at least one point.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
+
+/// Why [`RectilinearPolygon::new`] rejected its vertices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolygonError {
+    /// Fewer than 4 distinct vertices.
+    TooFewVertices(usize),
+    /// The edge from vertex `index` to the next one is diagonal or empty.
+    NonOrthogonal {
+        index: usize,
+        from: Point,
+        to: Point,
+    },
+    /// The edges starting at vertices `a` and `b` touch or cross.
+    SelfIntersecting { a: usize, b: usize },
+    /// Outlines `a` and `b` of a region touch or cross.
+    OutlinesIntersect { a: usize, b: usize },
+}
+
+impl Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewVertices(n) => write!(f, "polygon needs at least 4 vertices, got {n}"),
+            Self::NonOrthogonal { index, from, to } => write!(
+                f,
+                "edge {index} from {from:?} to {to:?} is not horizontal or vertical"
+            ),
+            Self::SelfIntersecting { a, b } => write!(f, "edges {a} and {b} intersect"),
+            Self::OutlinesIntersect { a, b } => write!(f, "outlines {a} and {b} intersect"),
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+/// A simple polygon whose edges are all horizontal or vertical.
+///
+/// The vertices form a closed loop: the last one connects back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectilinearPolygon {
+    vertices: Vec<Point>,
+}
+
+impl RectilinearPolygon {
+    /// Validates a loop of vertices. Repeating the first vertex at the end is
+    /// allowed.
+    pub fn new(vertices: impl IntoIterator<Item = Point>) -> Result<Self, PolygonError> {
+        let mut vertices: Vec<Point> = vertices.into_iter().collect();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 4 {
+            return Err(PolygonError::TooFewVertices(vertices.len()));
+        }
+
+        let polygon = Self { vertices };
+        polygon.check_orthogonal()?;
+        polygon.check_simple()?;
+        Ok(polygon)
+    }
+
+    fn check_orthogonal(&self) -> Result<(), PolygonError> {
+        match self
+            .edges()
+            .find_position(|&(a, b)| a == b || (a.x != b.x && a.y != b.y))
+        {
+            Some((index, (from, to))) => Err(PolygonError::NonOrthogonal { index, from, to }),
+            None => Ok(()),
+        }
+    }
+
+    /// Neighboring edges may only share their common vertex and must not
+    /// fold back onto each other; all other pairs must stay apart.
+    fn check_simple(&self) -> Result<(), PolygonError> {
+        let n = self.vertices.len();
+        let boxes: Vec<Rect> = self
+            .edges()
+            .map(|(a, b)| Rect::from_corners(a, b))
+            .collect();
+
+        for (a, b) in (0..n).tuple_combinations() {
+            let adjacent = b == a + 1 || (a == 0 && b == n - 1);
+            let crossing = if adjacent {
+                let (first, second) = if b == a + 1 { (a, b) } else { (b, a) };
+                let (p, q, r) = (
+                    self.vertices[first],
+                    self.vertices[second],
+                    self.vertices[(second + 1) % n],
+                );
+                let (d1, d2) = (q - p, r - q);
+                d1.x * d2.x + d1.y * d2.y < 0
+            } else {
+                boxes[a].intersects(&boxes[b])
+            };
+
+            if crossing {
+                return Err(PolygonError::SelfIntersecting { a, b });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// The edges as `(from, to)` pairs, closing the loop at the end.
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.vertices.iter().copied().circular_tuple_windows()
+    }
+
+    /// Enclosed area by the shoelace formula.
+    pub fn area(&self) -> u64 {
+        let twice: i64 = self.edges().map(|(a, b)| a.x * b.y - b.x * a.y).sum();
+        twice.unsigned_abs() / 2
+    }
+
+    /// Number of integer points on the boundary: the perimeter, as every
+    /// edge is axis-aligned.
+    pub fn boundary_points(&self) -> u64 {
+        self.edges().map(|(a, b)| a.manhattan(b)).sum()
+    }
+
+    /// Number of integer points strictly inside, by Pick's theorem.
+    pub fn interior_points(&self) -> u64 {
+        self.area() + 1 - self.boundary_points() / 2
+    }
+
+    /// Number of integer points inside or on the boundary.
+    pub fn lattice_points(&self) -> u64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Whether `p` lies inside or on the boundary.
+    pub fn contains(&self, p: Point) -> bool {
+        self.contains_point(p)
+    }
+
+    /// Whether every lattice point of `rect`, edge included, lies in the
+    /// polygon.
+    ///
+    /// Builds a [`TileMap`] each time; keep [`Self::tile_map`] around to
+    /// test many rectangles.
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        self.tile_map().covers(rect)
+    }
+
+    /// A map for testing rectangles against this shape repeatedly.
+    pub fn tile_map(&self) -> TileMap {
+        TileMap::new(self)
+    }
+
+    /// The rectangle with opposite corners on two vertices that covers the
+    /// most lattice points while staying inside the polygon.
+    pub fn largest_inscribed_rect(&self) -> Option<Rect> {
+        self.largest_rect()
+    }
+}
+
+impl Outline for RectilinearPolygon {
+    fn outline_edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.edges()
+    }
+
+    fn all_vertices(&self) -> impl Iterator<Item = Point> + '_ {
+        self.vertices.iter().copied()
+    }
+
+    fn fills(&self, winding: i64) -> bool {
+        winding != 0
+    }
+}
+
+/// Which points a set of possibly nested outlines fills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Points enclosed by an odd number of outlines.
+    #[default]
+    EvenOdd,
+    /// Points the outlines wind around at all, counting direction.
+    NonZero,
+}
+
+impl FillRule {
+    fn fills(self, winding: i64) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+impl FromStr for FillRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "evenodd" | "even-odd" => Ok(FillRule::EvenOdd),
+            "nonzero" => Ok(FillRule::NonZero),
+            _ => Err(format!("unknown fill rule: {s}")),
+        }
+    }
+}
+
+/// An area bounded by several disjoint outlines, possibly nested, filled
+/// by a [`FillRule`]. Points on an outline always belong to the region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectilinearRegion {
+    outlines: Vec<RectilinearPolygon>,
+    rule: FillRule,
+}
+
+impl RectilinearRegion {
+    /// Fails if two outlines touch or cross. Outlines one unit apart are
+    /// fine.
+    pub fn new(outlines: Vec<RectilinearPolygon>, rule: FillRule) -> Result<Self, PolygonError> {
+        let boxes: Vec<Vec<Rect>> = outlines
+            .iter()
+            .map(|o| o.edges().map(|(a, b)| Rect::from_corners(a, b)).collect())
+            .collect();
+
+        for (a, b) in (0..outlines.len()).tuple_combinations() {
+            let touching = boxes[a]
+                .iter()
+                .cartesian_product(&boxes[b])
+                .any(|(e, f)| e.intersects(f));
+            if touching {
+                return Err(PolygonError::OutlinesIntersect { a, b });
+            }
+        }
+
+        Ok(Self { outlines, rule })
+    }
+
+    pub fn outlines(&self) -> &[RectilinearPolygon] {
+        &self.outlines
+    }
+
+    pub fn rule(&self) -> FillRule {
+        self.rule
+    }
+
+    /// Whether `p` is filled or lies on an outline.
+    pub fn contains(&self, p: Point) -> bool {
+        self.contains_point(p)
+    }
+
+    /// Whether every lattice point of `rect`, edge included, is in the
+    /// region.
+    ///
+    /// Builds a [`TileMap`] each time; keep [`Self::tile_map`] around to
+    /// test many rectangles.
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        self.tile_map().covers(rect)
+    }
+
+    /// A map for testing rectangles against this shape repeatedly.
+    pub fn tile_map(&self) -> TileMap {
+        TileMap::new(self)
+    }
+
+    /// The rectangle with opposite corners on two outline vertices that
+    /// covers the most lattice points while staying inside the region.
+    pub fn largest_inscribed_rect(&self) -> Option<Rect> {
+        self.largest_rect()
+    }
+}
+
+impl Outline for RectilinearRegion {
+    fn outline_edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.outlines.iter().flat_map(|o| o.edges())
+    }
+
+    fn all_vertices(&self) -> impl Iterator<Item = Point> + '_ {
+        self.outlines
+            .iter()
+            .flat_map(|o| o.vertices.iter().copied())
+    }
+
+    fn fills(&self, winding: i64) -> bool {
+        self.rule.fills(winding)
+    }
+}
+
+/// Lattice-point queries shared by shapes bounded by axis-aligned edges.
+///
+/// Shapes are sets of lattice points, like the tiles of a floor: two
+/// parallel edges one unit apart leave no point outside between them.
+trait Outline {
+    /// Every directed edge of every outline.
+    fn outline_edges(&self) -> impl Iterator<Item = (Point, Point)> + '_;
+
+    fn all_vertices(&self) -> impl Iterator<Item = Point> + '_;
+
+    /// Whether a point off the outlines, wound around `winding` times, is
+    /// inside.
+    fn fills(&self, winding: i64) -> bool;
+
+    fn contains_point(&self, p: Point) -> bool {
+        let mut winding = 0;
+        for (a, b) in self.outline_edges() {
+            if Rect::from_corners(a, b).contains(p) {
+                return true;
+            }
+            // Count crossings of a ray towards +x, with each vertical edge
+            // covering its lower end only.
+            if a.x == b.x && a.x > p.x && (a.y.min(b.y)..a.y.max(b.y)).contains(&p.y) {
+                winding += if b.y > a.y { 1 } else { -1 };
+            }
+        }
+        self.fills(winding)
+    }
+
+    fn largest_rect(&self) -> Option<Rect>
+    where
+        Self: Sized,
+    {
+        let tiles = TileMap::new(self);
+        let vertices: Vec<Point> = self.all_vertices().collect();
+        let mut best: Option<Rect> = None;
+        for (&a, &b) in vertices.iter().tuple_combinations() {
+            let rect = Rect::from_corners(a, b);
+            // Only rectangles that would beat the best so far need the map.
+            let larger = best.is_none_or(|best| rect.lattice_points() > best.lattice_points());
+            if larger && tiles.covers(&rect) {
+                best = Some(rect);
+            }
+        }
+        best
+    }
+}
+
+/// The lattice points of a shape on a [`CompressedGrid`]: every vertex
+/// coordinate gets a column (and row) of its own, and so does each run of
+/// coordinates between two of them. All points in one cell are in the
+/// shape or all are out, so the map answers any number of rectangle
+/// queries without looking at the outlines again.
+#[derive(Debug, Clone)]
+pub struct TileMap {
+    outside: AreaSums,
+}
+
+impl TileMap {
+    fn new(shape: &impl Outline) -> Self {
+        let mut grid = CompressedGrid::new(
+            shape.all_vertices().map(|p| p.x),
+            shape.all_vertices().map(|p| p.y),
+            false,
+        );
+        let (width, height) = (grid.cells().width(), grid.cells().height());
+        let vertical: Vec<_> = shape.outline_edges().filter(|(a, b)| a.x == b.x).collect();
+
+        for row in 0..height {
+            // Each row is swept left to right, winding up crossings of a
+            // ray from the left, with the same half-open rule as
+            // `contains_point`.
+            let y = grid.y_span(row).start;
+            let mut crossings: Vec<(i64, i64)> = vertical
+                .iter()
+                .filter(|(a, b)| (a.y.min(b.y)..a.y.max(b.y)).contains(&y))
+                .map(|(a, b)| (a.x, if b.y > a.y { 1 } else { -1 }))
+                .collect();
+            crossings.sort_unstable();
+
+            let mut crossed = crossings.iter().peekable();
+            let mut winding = 0;
+            for column in 0..width {
+                let x = grid.x_span(column).start;
+                while let Some((_, sign)) = crossed.next_if(|(cx, _)| *cx < x) {
+                    winding += sign;
+                }
+                grid.cells_mut()[(column, row)] = shape.fills(winding);
+            }
+        }
+        for (a, b) in shape.outline_edges() {
+            grid.paint_segment(a, b, true);
+        }
+
+        Self {
+            outside: grid.area_sums(|&inside| !inside),
+        }
+    }
+
+    /// Whether every lattice point of `rect` is in the shape.
+    pub fn covers(&self, rect: &Rect) -> bool {
+        let area = self
+            .outside
+            .touched_area(rect.min.x..=rect.max.x, rect.min.y..=rect.max.y);
+        area == Some(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(i64, i64)]) -> Result<RectilinearPolygon, PolygonError> {
+        RectilinearPolygon::new(points.iter().map(|&p| Point::from(p)))
+    }
+
+    /// An L shape: a 4×4 square missing its top-right 2×2 quarter.
+    fn ell() -> RectilinearPolygon {
+        polygon(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)]).unwrap()
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(
+            polygon(&[(0, 0), (1, 0), (0, 1)]),
+            Err(PolygonError::TooFewVertices(3))
+        );
+        assert!(matches!(
+            polygon(&[(0, 0), (2, 0), (2, 2), (1, 3), (0, 2)]),
+            Err(PolygonError::NonOrthogonal { index: 2, .. })
+        ));
+        assert!(matches!(
+            polygon(&[(0, 0), (2, 0), (2, 2), (1, 2), (1, -1), (0, -1)]),
+            Err(PolygonError::SelfIntersecting { .. })
+        ));
+        assert!(matches!(
+            polygon(&[(0, 0), (3, 0), (1, 0), (1, 2), (0, 2)]),
+            Err(PolygonError::SelfIntersecting { .. })
+        ));
+        assert!(polygon(&[(0, 0), (2, 0), (2, 2), (0, 2), (0, 0)]).is_ok());
+    }
+
+    #[test]
+    fn measures() {
+        let l = ell();
+        assert_eq!(l.area(), 12);
+        assert_eq!(l.boundary_points(), 16);
+        assert_eq!(l.interior_points(), 5);
+        assert_eq!(l.lattice_points(), 21);
+    }
+
+    #[test]
+    fn containment() {
+        let l = ell();
+        assert!(l.contains(Point::new(1, 1)));
+        assert!(l.contains(Point::new(3, 2)));
+        assert!(!l.contains(Point::new(3, 1)));
+        assert!(!l.contains(Point::new(5, 4)));
+
+        let rect =
+            |a: (i64, i64), b: (i64, i64)| Rect::from_corners(Point::from(a), Point::from(b));
+        assert!(l.contains_rect(&rect((0, 0), (2, 4))));
+        assert!(l.contains_rect(&rect((0, 2), (4, 4))));
+        assert!(!l.contains_rect(&rect((0, 0), (4, 4))));
+        assert!(!l.contains_rect(&rect((1, 1), (3, 3))));
+        assert!(l.contains_rect(&rect((2, 0), (2, 4))));
+        assert!(!l.contains_rect(&rect((0, 1), (4, 1))));
+
+        let tiles = l.tile_map();
+        assert!(tiles.covers(&rect((1, 1), (1, 3))));
+        assert!(tiles.covers(&rect((1, 3), (3, 4))));
+        assert!(!tiles.covers(&rect((3, 3), (5, 3))));
+        assert!(!tiles.covers(&rect((-1, 0), (0, 0))));
+    }
+
+    #[test]
+    fn notch_between_vertices_is_outside() {
+        // A U shape: the gap between the arms is spanned by vertex corners.
+        let u = polygon(&[
+            (0, 0),
+            (1, 0),
+            (1, 3),
+            (3, 3),
+            (3, 0),
+            (4, 0),
+            (4, 4),
+            (0, 4),
+        ])
+        .unwrap();
+        let gap = Rect::from_corners(Point::new(1, 0), Point::new(3, 3));
+        assert!(!u.contains_rect(&gap));
+        // Either arm, 2×5 points each.
+        let best = u.largest_inscribed_rect().unwrap();
+        assert_eq!(best.lattice_points(), 10);
+        assert!(u.contains_rect(&best));
+    }
+
+    #[test]
+    fn fill_rules_on_nested_outlines() {
+        let outer = polygon(&[(0, 0), (10, 0), (10, 10), (0, 10)]).unwrap();
+        let same = polygon(&[(3, 3), (6, 3), (6, 6), (3, 6)]).unwrap();
+        let reversed = polygon(&[(3, 3), (3, 6), (6, 6), (6, 3)]).unwrap();
+        let region = |inner: &RectilinearPolygon, rule| {
+            RectilinearRegion::new(vec![outer.clone(), inner.clone()], rule).unwrap()
+        };
+        let hole = Point::new(4, 4);
+
+        assert!(!region(&same, FillRule::EvenOdd).contains(hole));
+        assert!(region(&same, FillRule::NonZero).contains(hole));
+        assert!(!region(&reversed, FillRule::NonZero).contains(hole));
+        assert!(region(&same, FillRule::EvenOdd).contains(Point::new(3, 4)));
+
+        let best = |inner, rule| {
+            region(inner, rule)
+                .largest_inscribed_rect()
+                .map(|r| r.lattice_points())
+        };
+        assert_eq!(best(&same, FillRule::EvenOdd), Some(40));
+        assert_eq!(best(&same, FillRule::NonZero), Some(121));
+        assert_eq!(best(&reversed, FillRule::NonZero), Some(40));
+
+        assert_eq!(
+            RectilinearRegion::new(vec![outer.clone(), outer.clone()], FillRule::EvenOdd),
+            Err(PolygonError::OutlinesIntersect { a: 0, b: 1 })
+        );
+    }
+
+    #[test]
+    fn adjacent_parallel_edges_leave_no_gap() {
+        // The slot between x = 1 and x = 2 holds no lattice point.
+        let slot = polygon(&[
+            (0, 0),
+            (1, 0),
+            (1, 3),
+            (2, 3),
+            (2, 0),
+            (3, 0),
+            (3, 4),
+            (0, 4),
+        ])
+        .unwrap();
+        let whole = Rect::from_corners(Point::new(0, 0), Point::new(3, 4));
+        assert!(slot.contains_rect(&whole));
+        assert_eq!(slot.largest_inscribed_rect(), Some(whole));
+    }
+
+    /// The largest vertex-cornered rectangle, checking every lattice point.
+    fn brute_force_best(region: &RectilinearRegion) -> u64 {
+        let vertices = region.outlines().iter().flat_map(|o| o.vertices().to_vec());
+        vertices
+            .tuple_combinations()
+            .map(|(a, b)| Rect::from_corners(a, b))
+            .filter(|r| {
+                (r.min.x..=r.max.x)
+                    .cartesian_product(r.min.y..=r.max.y)
+                    .all(|(x, y)| region.contains(Point::new(x, y)))
+            })
+            .map(|r| r.lattice_points())
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn adjacent_outlines() {
+        let square = |x0: i64, y0: i64, x1: i64, y1: i64| {
+            polygon(&[(x0, y0), (x1, y0), (x1, y1), (x0, y1)]).unwrap()
+        };
+        let side_by_side = vec![square(0, 0, 2, 3), square(3, 0, 5, 3)];
+        let nested = vec![square(0, 0, 6, 6), square(1, 1, 5, 5)];
+        let cases = [
+            (&side_by_side, FillRule::EvenOdd, 24),
+            (&side_by_side, FillRule::NonZero, 24),
+            (&nested, FillRule::EvenOdd, 6 * 2),
+            (&nested, FillRule::NonZero, 49),
+        ];
+
+        for (outlines, rule, expected) in cases {
+            let region = RectilinearRegion::new(outlines.clone(), rule).unwrap();
+            let best = region.largest_inscribed_rect().unwrap();
+            assert_eq!(best.lattice_points(), expected, "{rule:?}");
+            assert_eq!(brute_force_best(&region), expected, "{rule:?}");
+            assert!(region.contains_rect(&best));
+        }
+    }
+}
@@ -1,16 +1,104 @@
+use std::error::Error;
+use std::fmt::{self, Display, Write};
 use std::ops::{Index, IndexMut};
 
-const OFFSETS_4: &[(isize, isize)] = &[(0, -1), (1, 0), (0, 1), (-1, 0)];
-const OFFSETS_8: &[(isize, isize)] = &[
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-    (-1, 0),
-    (1, 0),
-    (-1, 1),
-    (0, 1),
-    (1, 1),
-];
+use crate::point::{Direction, Point};
+
+mod automaton;
+mod bits;
+mod compressed;
+mod neighborhood;
+mod ray;
+mod regions;
+mod sparse;
+mod summed_area;
+mod transform;
+mod view;
+
+pub use automaton::{Automaton, Outcome};
+pub use bits::BitGrid;
+pub use compressed::{AreaSums, CompressedGrid};
+pub use neighborhood::{Neighborhood, Topology};
+pub use regions::{Components, Region};
+pub use sparse::SparseGrid;
+pub use summed_area::{Summable, SummedArea};
+pub use view::GridView;
+
+/// Which cells count as adjacent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Orthogonal neighbors only.
+    Four,
+    /// Orthogonal and diagonal neighbors.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => &Direction::ALL_4,
+            Connectivity::Eight => &Direction::ALL_8,
+        }
+    }
+}
+
+/// Anything that can address a grid cell: `(x, y)` tuples or [`Point`]s.
+pub trait GridPos: Copy {
+    /// The cell coordinates, or `None` if a coordinate is negative.
+    fn xy(self) -> Option<(usize, usize)>;
+}
+
+impl GridPos for (usize, usize) {
+    #[inline]
+    fn xy(self) -> Option<(usize, usize)> {
+        Some(self)
+    }
+}
+
+impl GridPos for Point {
+    #[inline]
+    fn xy(self) -> Option<(usize, usize)> {
+        self.try_into().ok()
+    }
+}
+
+/// Why [`Grid::parse`] rejected its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError<E> {
+    /// The input contains no non-blank line.
+    Empty,
+    /// Line `y` has `found` cells instead of the `expected` width.
+    Ragged {
+        y: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell parser rejected `ch` at `(x, y)`: character `x` of line `y`,
+    /// counting lines from 0 including blank ones, and characters from the
+    /// first non-whitespace one.
+    Cell {
+        x: usize,
+        y: usize,
+        ch: char,
+        error: E,
+    },
+}
+
+impl<E: Display> Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "grid input is empty"),
+            Self::Ragged { y, expected, found } => {
+                write!(f, "line {y} has {found} cells, expected {expected}")
+            }
+            Self::Cell { x, y, ch, error } => {
+                write!(f, "invalid cell {ch:?} at ({x}, {y}): {error}")
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + Display> Error for ParseError<E> {}
 
 #[derive(Debug, Clone)]
 pub struct Grid<T> {
@@ -35,6 +123,44 @@ impl<T> Grid<T> {
         Self::new(width, height, data)
     }
 
+    /// Parses a character grid, one row per line.
+    ///
+    /// Lines are trimmed and blank lines are skipped, so indented samples
+    /// work as-is. All rows must have the same width. Errors report
+    /// positions in the input, see [`ParseError`].
+    pub fn parse<E>(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError<E>> {
+        let mut width = None;
+        let mut height = 0;
+        let mut data = Vec::new();
+
+        for (y, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let before = data.len();
+            for (x, ch) in line.chars().enumerate() {
+                let value = cell(ch).map_err(|error| ParseError::Cell { x, y, ch, error })?;
+                data.push(value);
+            }
+
+            let found = data.len() - before;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseError::Ragged { y, expected, found });
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) => Ok(Self::new(width, height, data)),
+            None => Err(ParseError::Empty),
+        }
+    }
+
     pub fn new(width: usize, height: usize, data: Vec<T>) -> Self {
         assert_eq!(width * height, data.len());
         Self {
@@ -44,6 +170,15 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Builds a grid by calling `f` for every `(x, y)` in reading order.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self::new(width, height, data)
+    }
+
     #[inline]
     pub fn width(&self) -> usize {
         self.width
@@ -60,7 +195,8 @@ impl<T> Grid<T> {
     }
 
     #[inline]
-    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+    fn index_of(&self, pos: impl GridPos) -> Option<usize> {
+        let (x, y) = pos.xy()?;
         if x < self.width && y < self.height {
             Some(self.idx(x, y))
         } else {
@@ -68,12 +204,60 @@ impl<T> Grid<T> {
         }
     }
 
-    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
-        self.index_of(x, y).map(|i| &self.data[i])
+    /// Whether `pos` lies on the grid.
+    pub fn contains(&self, pos: impl GridPos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    pub fn get(&self, pos: impl GridPos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, pos: impl GridPos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.data[i])
+    }
+
+    /// The point one step from `from` in `dir`, or `None` if it leaves the grid.
+    pub fn step(&self, from: Point, dir: Direction) -> Option<Point> {
+        let next = from.step(dir);
+        self.contains(next).then_some(next)
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.data.iter().map(f).collect())
+    }
+
+    /// Renders the grid as text, one line per row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        self.rows().fold(String::new(), |mut out, row| {
+            out.extend(row.iter().map(&mut f));
+            out.push('\n');
+            out
+        })
+    }
+
+    /// The cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside the grid");
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.data.iter().skip(x).step_by(self.width)
     }
 
-    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
-        self.index_of(x, y).map(|i| &mut self.data[i])
+    /// All rows, top to bottom. A grid of width 0 still has `height`
+    /// (empty) rows.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// All columns, left to right.
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.col(x))
     }
 
     /// All coordinates of the grid.
@@ -91,23 +275,45 @@ impl<T> Grid<T> {
         })
     }
 
+    /// A grid of the same shape with `f` applied to every cell's view, so
+    /// each new value can depend on the old neighborhood.
+    pub fn map_neighbors<U>(&self, f: impl FnMut(Cell<'_, T>) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.all().map(f).collect())
+    }
+
+    /// Replaces every cell matching `predicate` with `f(cell)`, returning how
+    /// many were replaced.
+    ///
+    /// Both closures see the grid as it was before any replacement.
+    pub fn update_where(
+        &mut self,
+        mut predicate: impl FnMut(Cell<'_, T>) -> bool,
+        mut f: impl FnMut(Cell<'_, T>) -> T,
+    ) -> usize {
+        let updates: Vec<((usize, usize), T)> = self
+            .all()
+            .filter(|&cell| predicate(cell))
+            .map(|cell| ((cell.x, cell.y), f(cell)))
+            .collect();
+
+        let count = updates.len();
+        updates.into_iter().for_each(|(p, value)| self[p] = value);
+        count
+    }
+
     #[inline]
     fn neighbors_with<'a>(
         &'a self,
         x: usize,
         y: usize,
-        offsets: &'static [(isize, isize)],
+        dirs: &'static [Direction],
     ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
-        offsets.iter().filter_map(move |(dx, dy)| {
-            let nx = x as isize + dx;
-            let ny = y as isize + dy;
-
-            if nx >= 0 && ny >= 0 && nx < self.width as isize && ny < self.height as isize {
-                let (ux, uy) = (nx as usize, ny as usize);
-                Some(((ux, uy), &self[(ux, uy)]))
-            } else {
-                None
-            }
+        dirs.iter().filter_map(move |&dir| {
+            let offset = dir.offset();
+            let nx = x.checked_add_signed(offset.x as isize)?;
+            let ny = y.checked_add_signed(offset.y as isize)?;
+            let i = self.index_of((nx, ny))?;
+            Some(((nx, ny), &self.data[i]))
         })
     }
 
@@ -117,7 +323,7 @@ impl<T> Grid<T> {
         x: usize,
         y: usize,
     ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
-        self.neighbors_with(x, y, OFFSETS_4)
+        self.neighbors_with(x, y, &Direction::ALL_4)
     }
 
     /// 8-neighbors of a coordinate (including diagonals).
@@ -126,32 +332,16 @@ impl<T> Grid<T> {
         x: usize,
         y: usize,
     ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
-        self.neighbors_with(x, y, OFFSETS_8)
+        self.neighbors_with(x, y, &Direction::ALL_8)
     }
 }
 
-impl Grid<bool> {
-    pub fn prefix_sum(&self) -> Grid<u64> {
-        let w = self.width();
-        let h = self.height();
-        let mut pref = Grid::new(w + 1, h + 1, vec![0u64; (w + 1) * (h + 1)]);
-
-        self.coords().for_each(|(x, y)| {
-            let v = u64::from(self[(x, y)]);
-            pref[(x + 1, y + 1)] = v + pref[(x, y + 1)] + pref[(x + 1, y)] - pref[(x, y)];
-        });
-
-        pref
-    }
-}
-
-impl Grid<u64> {
-    #[inline]
-    pub fn rect_sum(&self, x0: usize, y0: usize, x1: usize, y1: usize) -> u64 {
-        let [a, b, c, d] =
-            [(x1 + 1, y1 + 1), (x0, y1 + 1), (x1 + 1, y0), (x0, y0)].map(|coord| self[coord]);
-
-        a + d - b - c
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.rows().try_for_each(|row| {
+            row.iter().try_for_each(|&c| f.write_char(c.into()))?;
+            f.write_char('\n')
+        })
     }
 }
 
@@ -170,8 +360,24 @@ impl<T> IndexMut<(usize, usize)> for Grid<T> {
     }
 }
 
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        self.get(p)
+            .unwrap_or_else(|| panic!("point {p:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("point {p:?} is outside the grid"))
+    }
+}
+
 /// A view on a single cell of the grid, with handy neighbor methods.
-#[derive(Clone, Copy, Debug)]
+#[derive(Debug)]
 pub struct Cell<'g, T> {
     pub x: usize,
     pub y: usize,
@@ -179,14 +385,20 @@ pub struct Cell<'g, T> {
     grid: &'g Grid<T>,
 }
 
+// Only references inside, so copyable whatever `T` is.
+impl<T> Clone for Cell<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Cell<'_, T> {}
+
 impl<'g, T> Cell<'g, T> {
     #[inline]
-    fn neighbors_with(
-        self,
-        offsets: &'static [(isize, isize)],
-    ) -> impl Iterator<Item = Cell<'g, T>> + 'g {
+    fn neighbors_with(self, dirs: &'static [Direction]) -> impl Iterator<Item = Cell<'g, T>> + 'g {
         self.grid
-            .neighbors_with(self.x, self.y, offsets)
+            .neighbors_with(self.x, self.y, dirs)
             .map(move |((x, y), value)| Cell {
                 x,
                 y,
@@ -196,10 +408,122 @@ impl<'g, T> Cell<'g, T> {
     }
 
     pub fn neighbors_4(self) -> impl Iterator<Item = Cell<'g, T>> + 'g {
-        self.neighbors_with(OFFSETS_4)
+        self.neighbors_with(&Direction::ALL_4)
     }
 
     pub fn neighbors_8(self) -> impl Iterator<Item = Cell<'g, T>> + 'g {
-        self.neighbors_with(OFFSETS_8)
+        self.neighbors_with(&Direction::ALL_8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit(ch: char) -> Result<u8, String> {
+        ch.to_digit(10)
+            .map(|d| d as u8)
+            .ok_or_else(|| "not a digit".to_string())
+    }
+
+    #[test]
+    fn parse_skips_blank_lines_and_indentation() {
+        let grid = Grid::parse("\n  12\n\n  34\n  ", digit).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[(1, 1)], 4);
+    }
+
+    #[test]
+    fn parse_reports_positions() {
+        assert_eq!(
+            Grid::parse("12\n3x", digit).unwrap_err(),
+            ParseError::Cell {
+                x: 1,
+                y: 1,
+                ch: 'x',
+                error: "not a digit".to_string()
+            }
+        );
+        assert_eq!(
+            Grid::parse("123\n45", digit).unwrap_err(),
+            ParseError::Ragged {
+                y: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            Grid::parse("\n  12\n\n  3x", digit).unwrap_err(),
+            ParseError::Cell {
+                x: 1,
+                y: 3,
+                ch: 'x',
+                error: "not a digit".to_string()
+            }
+        );
+        assert_eq!(
+            Grid::parse("123\n\n45", digit).unwrap_err(),
+            ParseError::Ragged {
+                y: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(Grid::parse(" \n", digit).unwrap_err(), ParseError::Empty);
+    }
+
+    #[test]
+    fn points_address_cells() {
+        let mut grid = Grid::parse("12\n34", digit).unwrap();
+        let p = Point::new(1, 0);
+        assert_eq!(grid[p], 2);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.step(p, Direction::South), Some(Point::new(1, 1)));
+        assert_eq!(grid.step(p, Direction::East), None);
+        assert_eq!(grid.step(p, Direction::North), None);
+        grid[Point::new(0, 1)] = 9;
+        assert_eq!(grid.get((0, 1)), Some(&9));
+    }
+
+    #[test]
+    fn zero_width_grids_have_empty_rows() {
+        let grid: Grid<u8> = Grid::from_fn(0, 2, |_| unreachable!());
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.render(|_| '#'), "\n\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside the grid")]
+    fn row_out_of_range_panics() {
+        let grid: Grid<u8> = Grid::from_fn(0, 2, |_| unreachable!());
+        grid.row(2);
+    }
+
+    #[test]
+    fn render_round_trips() {
+        let input = "#.#\n.#.\n";
+        let grid = Grid::parse(input, |ch| Ok::<_, String>(ch == '#')).unwrap();
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), input);
+        assert_eq!(
+            grid.map(|&b| if b { b'#' } else { b'.' }).to_string(),
+            input
+        );
+    }
+
+    #[test]
+    fn neighborhood_updates_read_old_values() {
+        let mut grid = Grid::parse("#..\n.#.\n...", |ch| Ok::<_, ()>(ch == '#')).unwrap();
+        let counts = grid.map_neighbors(|c| c.neighbors_8().filter(|n| *n.value).count());
+        assert_eq!(counts[(1, 1)], 1);
+        assert_eq!(counts[(2, 2)], 1);
+
+        // Spreading must not chain within one update.
+        let spread =
+            grid.update_where(|c| !*c.value && c.neighbors_4().any(|n| *n.value), |_| true);
+        assert_eq!(spread, 4);
+        assert_eq!(
+            grid.render(|&b| if b { '#' } else { '.' }),
+            "##.\n###\n.#.\n"
+        );
     }
 }
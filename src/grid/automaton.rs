@@ -0,0 +1,297 @@
+//! Cellular automata: apply a local rule to every cell at once until the
+//! grid settles, repeats or runs out of steps.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+use super::{Cell, Connectivity, Grid};
+
+/// Why [`Automaton::run`] or [`Automaton::run_until_cycle`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The last step changed nothing.
+    Stable,
+    /// The step limit was reached first.
+    StepLimit,
+    /// The state after step `start + period` equals the one after step `start`.
+    Cycle { start: usize, period: usize },
+}
+
+/// A grid evolving under `rule`, which maps a cell and its neighbors to the
+/// cell's next value. Every step reads the previous generation only.
+pub struct Automaton<T, R> {
+    current: Grid<T>,
+    next: Grid<T>,
+    rule: R,
+    changes: Vec<usize>,
+    changed: Vec<(usize, usize)>,
+    incremental: Option<Incremental>,
+}
+
+/// Bookkeeping for re-evaluating only cells near the last changes.
+struct Incremental {
+    neighborhood: Connectivity,
+    dirty: Vec<(usize, usize)>,
+    queued: Grid<bool>,
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Clone + PartialEq,
+    R: FnMut(Cell<'_, T>) -> T,
+{
+    pub fn new(grid: Grid<T>, rule: R) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            rule,
+            changes: Vec::new(),
+            changed: Vec::new(),
+            incremental: None,
+        }
+    }
+
+    /// Only re-evaluates cells within `neighborhood` of a cell that changed
+    /// in the previous step. The rule must not look any further than that.
+    pub fn incremental(mut self, neighborhood: Connectivity) -> Self {
+        self.incremental = Some(Incremental {
+            neighborhood,
+            dirty: self.current.coords().collect(),
+            queued: self.current.map(|_| false),
+        });
+        self
+    }
+
+    /// The current generation.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    /// Number of steps taken so far.
+    pub fn steps(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// How many cells each step changed.
+    pub fn changes(&self) -> &[usize] {
+        &self.changes
+    }
+
+    /// Advances one generation and returns the number of changed cells.
+    pub fn step(&mut self) -> usize {
+        let Self {
+            current,
+            next,
+            rule,
+            changed,
+            incremental,
+            ..
+        } = self;
+
+        match incremental {
+            None => {
+                changed.clear();
+                changed.extend(
+                    current
+                        .coords()
+                        .filter(|&p| Self::update(rule, current, next, p)),
+                );
+            }
+            Some(inc) => {
+                // `next` still holds the generation before `current`; only
+                // the cells changed since then are out of date.
+                changed.iter().for_each(|&p| next[p] = current[p].clone());
+                changed.clear();
+                changed.extend(
+                    inc.dirty
+                        .drain(..)
+                        .filter(|&p| Self::update(rule, current, next, p)),
+                );
+                inc.mark_dirty(current, changed);
+            }
+        }
+
+        mem::swap(current, next);
+        self.changes.push(self.changed.len());
+        self.changed.len()
+    }
+
+    /// Writes the rule's verdict for `p` into `next`, reporting a change.
+    fn update(rule: &mut R, current: &Grid<T>, next: &mut Grid<T>, p: (usize, usize)) -> bool {
+        let cell = Cell {
+            x: p.0,
+            y: p.1,
+            value: &current[p],
+            grid: current,
+        };
+        next[p] = rule(cell);
+        next[p] != current[p]
+    }
+
+    /// Steps until the grid stops changing or `max_steps` steps have been
+    /// taken in total.
+    pub fn run(&mut self, max_steps: usize) -> Outcome {
+        while self.steps() < max_steps {
+            if self.step() == 0 {
+                return Outcome::Stable;
+            }
+        }
+        Outcome::StepLimit
+    }
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Clone + PartialEq + Hash,
+    R: FnMut(Cell<'_, T>) -> T,
+{
+    /// Like [`Automaton::run`], but also stops once a generation repeats.
+    ///
+    /// Every generation is kept, bucketed by hash, so that a repeat can be
+    /// confirmed cell by cell.
+    pub fn run_until_cycle(&mut self, max_steps: usize) -> Outcome {
+        let mut seen: HashMap<u64, Vec<(Vec<T>, usize)>> = HashMap::new();
+        self.remember(&mut seen);
+
+        while self.steps() < max_steps {
+            if self.step() == 0 {
+                return Outcome::Stable;
+            }
+            if let Some(start) = self.remember(&mut seen) {
+                return Outcome::Cycle {
+                    start,
+                    period: self.steps() - start,
+                };
+            }
+        }
+        Outcome::StepLimit
+    }
+
+    /// Records the current generation, or returns the step at which the
+    /// same generation was seen before.
+    fn remember(&self, seen: &mut HashMap<u64, Vec<(Vec<T>, usize)>>) -> Option<usize> {
+        let mut hasher = DefaultHasher::new();
+        self.current.data.hash(&mut hasher);
+
+        let bucket = seen.entry(hasher.finish()).or_default();
+        if let Some((_, step)) = bucket.iter().find(|(data, _)| *data == self.current.data) {
+            return Some(*step);
+        }
+        bucket.push((self.current.data.clone(), self.steps()));
+        None
+    }
+}
+
+impl Incremental {
+    /// Queues the changed cells and their neighbors for the next step.
+    fn mark_dirty<T>(&mut self, grid: &Grid<T>, changed: &[(usize, usize)]) {
+        for &(x, y) in changed {
+            let around = grid
+                .neighbors_with(x, y, self.neighborhood.directions())
+                .map(|(p, _)| p);
+
+            for p in [(x, y)].into_iter().chain(around) {
+                if !mem::replace(&mut self.queued[p], true) {
+                    self.dirty.push(p);
+                }
+            }
+        }
+        self.dirty.iter().for_each(|&p| self.queued[p] = false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cell: Cell<'_, bool>) -> bool {
+        let alive = cell.neighbors_8().filter(|n| *n.value).count();
+        alive == 3 || (*cell.value && alive == 2)
+    }
+
+    fn grid(input: &str) -> Grid<bool> {
+        Grid::parse(input, |c| Ok::<_, ()>(c == '#')).unwrap()
+    }
+
+    fn show(grid: &Grid<bool>) -> String {
+        grid.render(|&alive| if alive { '#' } else { '.' })
+    }
+
+    #[test]
+    fn blinker_cycles() {
+        let blinker = grid(
+            "
+            .....
+            ..#..
+            ..#..
+            ..#..
+            .....
+            ",
+        );
+        let mut cycling = Automaton::new(blinker.clone(), life);
+        assert_eq!(
+            cycling.run_until_cycle(10),
+            Outcome::Cycle {
+                start: 0,
+                period: 2
+            }
+        );
+        assert_eq!(cycling.changes(), [4, 4]);
+        assert_eq!(show(cycling.grid()), show(&blinker));
+
+        let mut limited = Automaton::new(blinker, life);
+        assert_eq!(limited.run(3), Outcome::StepLimit);
+        assert_eq!(limited.steps(), 3);
+    }
+
+    /// A cell value whose hash ignores the value, so every generation
+    /// collides.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Colliding(u8);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, _: &mut H) {}
+    }
+
+    #[test]
+    fn hash_collisions_are_not_cycles() {
+        let grid = Grid::new(1, 1, vec![Colliding(0)]);
+        let mut counter = Automaton::new(grid, |c: Cell<'_, Colliding>| {
+            Colliding((c.value.0 + 1) % 3)
+        });
+        assert_eq!(
+            counter.run_until_cycle(10),
+            Outcome::Cycle {
+                start: 0,
+                period: 3
+            }
+        );
+    }
+
+    #[test]
+    fn incremental_matches_full_rescans() {
+        let glider = grid(
+            "
+            .#......
+            ..#.....
+            ###.....
+            ........
+            ........
+            ........
+            ",
+        );
+        let mut full = Automaton::new(glider.clone(), life);
+        let mut incremental = Automaton::new(glider, life).incremental(Connectivity::Eight);
+
+        assert_eq!(full.run(50), Outcome::Stable);
+        assert_eq!(incremental.run(50), Outcome::Stable);
+        assert_eq!(incremental.changes(), full.changes());
+        assert_eq!(show(incremental.grid()), show(full.grid()));
+    }
+}
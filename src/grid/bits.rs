@@ -0,0 +1,326 @@
+//! Bit-packed boolean grids with word-parallel operations.
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+use super::Grid;
+use crate::point::Direction;
+
+/// A boolean grid storing one bit per cell, each row as a run of `u64` words.
+///
+/// Bit `x % 64` of word `x / 64` holds column `x`; the unused high bits of a
+/// row's last word are always zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// An all-`false` grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The value at `(x, y)`, or `None` outside the grid.
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<bool> {
+        (x < self.width && y < self.height)
+            .then(|| self.words[y * self.words_per_row + x / 64] >> (x % 64) & 1 == 1)
+    }
+
+    /// Sets the value at `(x, y)`.
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn set(&mut self, (x, y): (usize, usize), value: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "{:?} is outside the grid",
+            (x, y)
+        );
+        let word = &mut self.words[y * self.words_per_row + x / 64];
+        let bit = 1 << (x % 64);
+        if value {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    /// Number of `true` cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Positions of the `true` cells in reading order.
+    pub fn ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (y, x0) = (i / self.words_per_row, i % self.words_per_row * 64);
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    (x0 + bit, y)
+                })
+            })
+        })
+    }
+
+    /// Cells set here but not in `other`.
+    pub fn and_not(&self, other: &BitGrid) -> BitGrid {
+        self.zip_words(other, |a, b| a & !b)
+    }
+
+    /// Moves every cell one step towards `dir`; cells pushed over the edge
+    /// are dropped and the vacated edge is `false`.
+    pub fn shifted(&self, dir: Direction) -> BitGrid {
+        let offset = dir.offset();
+        let mut out = BitGrid::new(self.width, self.height);
+
+        for y in 0..self.height {
+            let Some(src) = y.checked_add_signed(-offset.y as isize) else {
+                continue;
+            };
+            if src >= self.height {
+                continue;
+            }
+
+            let from = self.row(src);
+            for (i, word) in out.row_mut(y).iter_mut().enumerate() {
+                *word = match offset.x {
+                    1 => shifted_east(from, i),
+                    -1 => shifted_west(from, i),
+                    _ => from[i],
+                };
+            }
+            out.clear_padding(y);
+        }
+
+        out
+    }
+
+    /// Marks the cells with fewer than `k` of their 8 neighbors set.
+    ///
+    /// Neighbor counts are kept as four bit planes per word, so 64 cells
+    /// are counted and compared at once.
+    pub fn neighbor_count_below(&self, k: u32) -> BitGrid {
+        let mut out = BitGrid::new(self.width, self.height);
+        if k > 8 {
+            out.words.fill(!0);
+            (0..self.height).for_each(|y| out.clear_padding(y));
+            return out;
+        }
+
+        for y in 0..self.height {
+            let rows = [y.checked_sub(1), Some(y), Some(y + 1)];
+            let rows = rows.map(|r| r.filter(|&r| r < self.height).map(|r| self.row(r)));
+
+            for i in 0..self.words_per_row {
+                let mut planes = [0u64; 4];
+                for (dy, row) in rows.iter().enumerate() {
+                    let Some(row) = row else { continue };
+                    add_bit(&mut planes, shifted_east(row, i));
+                    add_bit(&mut planes, shifted_west(row, i));
+                    if dy != 1 {
+                        add_bit(&mut planes, row[i]);
+                    }
+                }
+
+                out.row_mut(y)[i] = (0..k).fold(0, |below, count| {
+                    below
+                        | planes.iter().enumerate().fold(!0, |eq, (b, &plane)| {
+                            eq & if count >> b & 1 == 1 { plane } else { !plane }
+                        })
+                });
+            }
+            out.clear_padding(y);
+        }
+
+        out
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Zeroes the bits past the grid's width in row `y`.
+    fn clear_padding(&mut self, y: usize) {
+        let used = self.width % 64;
+        if let (Some(last), true) = (self.row_mut(y).last_mut(), used != 0) {
+            *last &= (1 << used) - 1;
+        }
+    }
+
+    fn zip_words(&self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) -> BitGrid {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "bit grids differ in size"
+        );
+        BitGrid {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            ..*self
+        }
+    }
+}
+
+/// Word `i` of `row` with every cell moved one column east.
+#[inline]
+fn shifted_east(row: &[u64], i: usize) -> u64 {
+    let carry = if i > 0 { row[i - 1] >> 63 } else { 0 };
+    row[i] << 1 | carry
+}
+
+/// Word `i` of `row` with every cell moved one column west.
+#[inline]
+fn shifted_west(row: &[u64], i: usize) -> u64 {
+    let carry = row.get(i + 1).map_or(0, |w| w << 63);
+    row[i] >> 1 | carry
+}
+
+/// Adds one bit per lane to the bit-sliced counters in `planes`.
+#[inline]
+fn add_bit(planes: &mut [u64; 4], mut carry: u64) {
+    for plane in planes {
+        let next = *plane & carry;
+        *plane ^= carry;
+        carry = next;
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width(), grid.height());
+        grid.coords()
+            .filter(|&p| grid[p])
+            .for_each(|p| bits.set(p, true));
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        Grid::from_fn(bits.width, bits.height, |p| bits.get(p) == Some(true))
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: Self) -> BitGrid {
+        self.zip_words(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: Self) -> BitGrid {
+        self.zip_words(rhs, |a, b| a | b)
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        *self = &*self & rhs;
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        *self = &*self | rhs;
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut out = BitGrid {
+            words: self.words.iter().map(|w| !w).collect(),
+            ..*self
+        };
+        (0..out.height).for_each(|y| out.clear_padding(y));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A deterministic pattern that spans several words per row.
+    fn pattern(width: usize, height: usize) -> Grid<bool> {
+        Grid::from_fn(width, height, |(x, y)| (x * 7 + y * 13 + x * y) % 5 < 2)
+    }
+
+    #[test]
+    fn round_trips_and_counts() {
+        let grid = pattern(130, 9);
+        let bits = BitGrid::from(&grid);
+        let show = |g: &Grid<bool>| g.render(|&b| if b { '#' } else { '.' });
+        assert_eq!(show(&Grid::from(&bits)), show(&grid));
+        assert_eq!(bits.count_ones(), grid.all().filter(|c| *c.value).count());
+        assert_eq!(bits.ones().count(), bits.count_ones());
+        assert_eq!((!&bits).count_ones(), 130 * 9 - bits.count_ones());
+        assert_eq!((&bits & &!&bits).count_ones(), 0);
+    }
+
+    #[test]
+    fn shifts_move_cells() {
+        let mut bits = BitGrid::new(70, 3);
+        bits.set((63, 1), true);
+        bits.set((69, 0), true);
+
+        let east = bits.shifted(Direction::East);
+        assert_eq!(east.ones().collect::<Vec<_>>(), [(64, 1)]);
+
+        let south_west = bits.shifted(Direction::SouthWest);
+        assert_eq!(south_west.ones().collect::<Vec<_>>(), [(68, 1), (62, 2)]);
+    }
+
+    #[test]
+    fn neighbor_counts_match_naive() {
+        let grid = pattern(130, 9);
+        let bits = BitGrid::from(&grid);
+
+        for k in 0..=9 {
+            let below = bits.neighbor_count_below(k);
+            for cell in grid.all() {
+                let n = cell.neighbors_8().filter(|c| *c.value).count() as u32;
+                assert_eq!(
+                    below.get((cell.x, cell.y)),
+                    Some(n < k),
+                    "{k} at {:?}",
+                    (cell.x, cell.y)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Coordinate-compressed grids for huge, sparse coordinate ranges.
+
+use std::ops::{Range, RangeInclusive};
+
+use super::{Grid, SummedArea};
+use crate::point::Point;
+
+/// A grid over a huge plane where only a few coordinates matter.
+///
+/// Every interesting coordinate `v` gets its own unit cell `[v, v + 1)`;
+/// the gaps between them collapse into one cell each. One extra cell of
+/// margin surrounds everything, so [`Grid::flood_from_border`] can reach
+/// around painted shapes.
+#[derive(Debug, Clone)]
+pub struct CompressedGrid<T> {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    cells: Grid<T>,
+}
+
+impl<T: Clone> CompressedGrid<T> {
+    /// Compresses the given coordinates, filling every cell with `fill`.
+    ///
+    /// Panics if either axis has no coordinates.
+    pub fn new(
+        xs: impl IntoIterator<Item = i64>,
+        ys: impl IntoIterator<Item = i64>,
+        fill: T,
+    ) -> Self {
+        let xs = Self::axis(xs);
+        let ys = Self::axis(ys);
+        let (w, h) = (xs.len() - 1, ys.len() - 1);
+
+        Self {
+            cells: Grid::new(w, h, vec![fill; w * h]),
+            xs,
+            ys,
+        }
+    }
+
+    fn axis(values: impl IntoIterator<Item = i64>) -> Vec<i64> {
+        let mut bounds: Vec<i64> = values.into_iter().flat_map(|v| [v, v + 1]).collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let (min, max) = (bounds.first().copied(), bounds.last().copied());
+        let (min, max) = min.zip(max).expect("compressed axis needs coordinates");
+        bounds.insert(0, min - 1);
+        bounds.push(max + 1);
+        bounds
+    }
+
+    /// Paints every cell touched by the axis-aligned segment `from → to`,
+    /// both ends included.
+    ///
+    /// Panics if the segment is diagonal or leaves the grid.
+    pub fn paint_segment(&mut self, from: Point, to: Point, value: T) {
+        assert!(
+            from.x == to.x || from.y == to.y,
+            "Non-orthogonal segment: {from:?} -> {to:?}"
+        );
+        self.paint_rect(from, to, value);
+    }
+
+    /// Paints every cell touched by the rectangle spanned by two corners,
+    /// both included.
+    ///
+    /// Panics if the rectangle leaves the grid.
+    pub fn paint_rect(&mut self, a: Point, b: Point, value: T) {
+        let xs = Self::index_range(&self.xs, a.x.min(b.x)..=a.x.max(b.x));
+        let ys = Self::index_range(&self.ys, a.y.min(b.y)..=a.y.max(b.y));
+
+        ys.flat_map(|y| xs.clone().map(move |x| (x, y)))
+            .for_each(|p| self.cells[p] = value.clone());
+    }
+
+    /// Cells covering the real range `vs` on one axis.
+    fn index_range(bounds: &[i64], vs: RangeInclusive<i64>) -> Range<usize> {
+        let index = |v| {
+            index_in(bounds, v)
+                .unwrap_or_else(|| panic!("coordinate {v} is outside the compressed grid"))
+        };
+        index(*vs.start())..index(*vs.end()) + 1
+    }
+}
+
+impl<T> CompressedGrid<T> {
+    /// The compressed cells.
+    pub fn cells(&self) -> &Grid<T> {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut Grid<T> {
+        &mut self.cells
+    }
+
+    /// Column of the cell containing `x`, if inside the grid.
+    pub fn x_index(&self, x: i64) -> Option<usize> {
+        index_in(&self.xs, x)
+    }
+
+    /// Row of the cell containing `y`, if inside the grid.
+    pub fn y_index(&self, y: i64) -> Option<usize> {
+        index_in(&self.ys, y)
+    }
+
+    /// Cell containing `p`, if inside the grid.
+    pub fn index_of(&self, p: Point) -> Option<(usize, usize)> {
+        Some((self.x_index(p.x)?, self.y_index(p.y)?))
+    }
+
+    /// Real x coordinates covered by column `i`.
+    pub fn x_span(&self, i: usize) -> Range<i64> {
+        self.xs[i]..self.xs[i + 1]
+    }
+
+    /// Real y coordinates covered by row `j`.
+    pub fn y_span(&self, j: usize) -> Range<i64> {
+        self.ys[j]..self.ys[j + 1]
+    }
+
+    /// Real area covered by cell `(i, j)`.
+    pub fn cell_area(&self, (i, j): (usize, usize)) -> u128 {
+        let w = self.xs[i + 1] - self.xs[i];
+        let h = self.ys[j + 1] - self.ys[j];
+        w as u128 * h as u128
+    }
+
+    /// Area-weighted sums over the cells for which `include` holds.
+    pub fn area_sums(&self, include: impl Fn(&T) -> bool) -> AreaSums {
+        let sums = SummedArea::new(
+            &self.cells,
+            |p, v| {
+                if include(v) { self.cell_area(p) } else { 0 }
+            },
+        );
+
+        AreaSums {
+            xs: self.xs.clone(),
+            ys: self.ys.clone(),
+            sums,
+        }
+    }
+}
+
+/// Index of the cell whose span in `bounds` contains `v`.
+fn index_in(bounds: &[i64], v: i64) -> Option<usize> {
+    let i = bounds.partition_point(|&b| b <= v);
+    (i > 0 && i < bounds.len()).then(|| i - 1)
+}
+
+/// Real-area sums over a [`CompressedGrid`], see [`CompressedGrid::area_sums`].
+#[derive(Debug, Clone)]
+pub struct AreaSums {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    sums: SummedArea<u128>,
+}
+
+impl AreaSums {
+    /// Included area within the real rectangle `xs × ys`, ends included.
+    ///
+    /// Both ranges must start and end on cell boundaries, e.g. on
+    /// coordinates the grid was built from. Returns `None` otherwise.
+    pub fn area(&self, xs: RangeInclusive<i64>, ys: RangeInclusive<i64>) -> Option<u128> {
+        let bound = |axis: &[i64], v: i64| axis.binary_search(&v).ok();
+        let (x0, x1) = (
+            bound(&self.xs, *xs.start())?,
+            bound(&self.xs, xs.end() + 1)?,
+        );
+        let (y0, y1) = (
+            bound(&self.ys, *ys.start())?,
+            bound(&self.ys, ys.end() + 1)?,
+        );
+        self.sums.sum(x0..x1, y0..y1)
+    }
+
+    /// Included area of every cell the real rectangle `xs × ys` touches,
+    /// ends included, counting partly covered cells whole. Returns `None`
+    /// if the rectangle leaves the grid.
+    pub fn touched_area(&self, xs: RangeInclusive<i64>, ys: RangeInclusive<i64>) -> Option<u128> {
+        let cells = |axis: &[i64], vs: RangeInclusive<i64>| {
+            Some(index_in(axis, *vs.start())?..=index_in(axis, *vs.end())?)
+        };
+        self.sums
+            .sum_inclusive(cells(&self.xs, xs)?, cells(&self.ys, ys)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compresses_axes() {
+        let grid = CompressedGrid::new([10, 1000], [-5], '.');
+        assert_eq!(grid.cells().width(), 5);
+        assert_eq!(grid.cells().height(), 3);
+
+        assert_eq!(grid.x_index(10), Some(1));
+        assert_eq!(grid.x_index(500), Some(2));
+        assert_eq!(grid.x_index(1000), Some(3));
+        assert_eq!(grid.x_index(9), Some(0));
+        assert_eq!(grid.x_index(8), None);
+        assert_eq!(grid.x_index(1002), None);
+
+        assert_eq!(grid.x_span(2), 11..1000);
+        assert_eq!(grid.cell_area((2, 1)), 989);
+    }
+
+    #[test]
+    fn paints_and_sums_real_area() {
+        let mut grid = CompressedGrid::new([0, 100, 200], [0, 50], false);
+        grid.paint_rect(Point::new(0, 0), Point::new(100, 50), true);
+        grid.paint_segment(Point::new(200, 0), Point::new(200, 50), true);
+
+        let sums = grid.area_sums(|&v| v);
+        assert_eq!(sums.area(0..=100, 0..=50), Some(101 * 51));
+        assert_eq!(sums.area(0..=200, 0..=50), Some(101 * 51 + 51));
+        assert_eq!(sums.area(0..=99, 0..=50), Some(100 * 51));
+        assert_eq!(sums.area(0..=50, 0..=50), None);
+        assert_eq!(sums.touched_area(0..=50, 0..=50), Some(100 * 51));
+        assert_eq!(sums.touched_area(150..=150, 0..=0), Some(0));
+        assert_eq!(sums.touched_area(0..=202, 0..=0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Non-orthogonal")]
+    fn rejects_diagonal_segments() {
+        let mut grid = CompressedGrid::new([0, 1], [0, 1], 0u8);
+        grid.paint_segment(Point::new(0, 0), Point::new(1, 1), 1);
+    }
+}
@@ -0,0 +1,175 @@
+//! Neighborhood shapes and edge behaviour for neighbor lookups.
+
+use super::{Cell, Connectivity, Grid};
+use crate::point::Point;
+
+/// A set of offsets around a cell, never including the cell itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighborhood {
+    offsets: Vec<Point>,
+}
+
+impl Neighborhood {
+    /// Cells within Manhattan distance `radius`, in reading order.
+    pub fn von_neumann(radius: u32) -> Self {
+        Self::within(radius, |p| p.manhattan(Point::ORIGIN) <= radius as u64)
+    }
+
+    /// Cells within Chebyshev distance `radius`, in reading order.
+    pub fn moore(radius: u32) -> Self {
+        Self::within(radius, |_| true)
+    }
+
+    /// The 8 chess knight moves, in reading order.
+    pub fn knight() -> Self {
+        Self::within(2, |p| p.x.abs() + p.y.abs() == 3 && p.x != 0 && p.y != 0)
+    }
+
+    /// Arbitrary offsets, kept in the given order. The origin is dropped.
+    pub fn custom(offsets: impl IntoIterator<Item = Point>) -> Self {
+        Self {
+            offsets: offsets
+                .into_iter()
+                .filter(|&p| p != Point::ORIGIN)
+                .collect(),
+        }
+    }
+
+    fn within(radius: u32, keep: impl Fn(Point) -> bool) -> Self {
+        let radius = radius as i64;
+        let square =
+            (-radius..=radius).flat_map(|y| (-radius..=radius).map(move |x| Point::new(x, y)));
+        Self::custom(square.filter(|&p| keep(p)))
+    }
+
+    pub fn offsets(&self) -> &[Point] {
+        &self.offsets
+    }
+}
+
+impl From<Connectivity> for Neighborhood {
+    fn from(connectivity: Connectivity) -> Self {
+        Self::custom(connectivity.directions().iter().map(|d| d.offset()))
+    }
+}
+
+/// What lies beyond the edges of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    /// Nothing; neighbors off the grid are skipped.
+    #[default]
+    Bounded,
+    /// The opposite edge, as on a torus.
+    Wrap,
+    /// A mirror image of the grid: one step past an edge lands on the edge
+    /// cell itself, two steps on the cell next to it, and so on.
+    Reflect,
+}
+
+impl Topology {
+    /// Maps `p` onto a `width × height` grid, or `None` if it falls off.
+    pub fn resolve(self, p: Point, width: usize, height: usize) -> Option<(usize, usize)> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some((self.axis(p.x, width)?, self.axis(p.y, height)?))
+    }
+
+    fn axis(self, v: i64, len: usize) -> Option<usize> {
+        let len = len as i64;
+        let v = match self {
+            Topology::Bounded => (0..len).contains(&v).then_some(v)?,
+            Topology::Wrap => v.rem_euclid(len),
+            Topology::Reflect => {
+                let m = v.rem_euclid(2 * len);
+                if m < len { m } else { 2 * len - 1 - m }
+            }
+        };
+        Some(v as usize)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Neighbors of `(x, y)` in `neighborhood`, in its order.
+    ///
+    /// With [`Topology::Wrap`] or [`Topology::Reflect`] on a small grid the
+    /// same cell, or `(x, y)` itself, can show up more than once.
+    pub fn neighbors<'a>(
+        &'a self,
+        (x, y): (usize, usize),
+        neighborhood: &'a Neighborhood,
+        topology: Topology,
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        let origin = Point::from((x, y));
+        neighborhood.offsets.iter().filter_map(move |&offset| {
+            let p = topology.resolve(origin + offset, self.width, self.height)?;
+            Some((p, &self[p]))
+        })
+    }
+}
+
+impl<'g, T> Cell<'g, T> {
+    /// Neighbors in `neighborhood`, see [`Grid::neighbors`].
+    pub fn neighbors(
+        self,
+        neighborhood: &'g Neighborhood,
+        topology: Topology,
+    ) -> impl Iterator<Item = Cell<'g, T>> + 'g {
+        let grid = self.grid;
+        grid.neighbors((self.x, self.y), neighborhood, topology)
+            .map(move |((x, y), value)| Cell { x, y, value, grid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes() {
+        assert_eq!(Neighborhood::von_neumann(1).offsets().len(), 4);
+        assert_eq!(Neighborhood::von_neumann(2).offsets().len(), 12);
+        assert_eq!(Neighborhood::moore(2).offsets().len(), 24);
+        assert_eq!(
+            Neighborhood::knight().offsets(),
+            [
+                (-1, -2),
+                (1, -2),
+                (-2, -1),
+                (2, -1),
+                (-2, 1),
+                (2, 1),
+                (-1, 2),
+                (1, 2)
+            ]
+            .map(|(x, y)| Point::new(x, y))
+        );
+        assert_eq!(
+            Neighborhood::from(Connectivity::Four).offsets()[0],
+            Point::new(0, -1)
+        );
+    }
+
+    #[test]
+    fn topologies() {
+        let grid = Grid::parse("abc\ndef", Ok::<_, ()>).unwrap();
+        let moore = Neighborhood::moore(1);
+        let around = |topology| {
+            grid.neighbors((0, 0), &moore, topology)
+                .map(|(_, &c)| c)
+                .collect::<String>()
+        };
+
+        assert_eq!(around(Topology::Bounded), "bde");
+        assert_eq!(around(Topology::Wrap), "fdecbfde");
+        assert_eq!(around(Topology::Reflect), "aababdde");
+
+        let cell = grid.all().nth(4).unwrap();
+        let knight = Neighborhood::knight();
+        let jumps: Vec<_> = cell
+            .neighbors(&knight, Topology::Wrap)
+            .map(|c| *c.value)
+            .collect();
+        assert_eq!(jumps.len(), 8);
+    }
+}
@@ -0,0 +1,112 @@
+//! Straight-line walks across a grid: rays, first hits and line of sight.
+
+use super::Grid;
+use crate::point::{Direction, Point};
+
+impl<T> Grid<T> {
+    /// The cells from `from` towards `dir`, excluding `from` itself, up to
+    /// the edge of the grid.
+    pub fn ray(&self, from: Point, dir: Direction) -> impl Iterator<Item = (Point, &T)> + '_ {
+        std::iter::successors(self.step(from, dir), move |&p| self.step(p, dir))
+            .map(move |p| (p, &self[p]))
+    }
+
+    /// Like [`Self::ray`], but ends at the first cell matching `stop`. That
+    /// cell is yielded as the last item.
+    pub fn ray_until<'a>(
+        &'a self,
+        from: Point,
+        dir: Direction,
+        mut stop: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        let mut done = false;
+        self.ray(from, dir).map_while(move |(p, v)| {
+            if done {
+                return None;
+            }
+            done = stop(v);
+            Some((p, v))
+        })
+    }
+
+    /// The first cell past `from` towards `dir` matching `predicate`.
+    pub fn first_hit(
+        &self,
+        from: Point,
+        dir: Direction,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> Option<(Point, &T)> {
+        self.ray(from, dir).find(|(_, v)| predicate(v))
+    }
+
+    /// For each of the 8 directions, the first cell matching `predicate`
+    /// as seen from `from`. Directions that reach the edge first are left out.
+    pub fn visible_from<'a>(
+        &'a self,
+        from: Point,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Direction, Point, &'a T)> + 'a {
+        Direction::ALL_8.into_iter().filter_map(move |dir| {
+            let (p, v) = self.first_hit(from, dir, &predicate)?;
+            Some((dir, p, v))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse(
+            "
+            #.....
+            ..#...
+            ......
+            ..X.#.
+            ",
+            Ok::<_, ()>,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rays_stop_at_the_edge() {
+        let g = grid();
+        let x = Point::new(2, 3);
+        let up: String = g.ray(x, Direction::North).map(|(_, &c)| c).collect();
+        assert_eq!(up, ".#.");
+        assert_eq!(g.ray(x, Direction::South).count(), 0);
+
+        let hit = g.first_hit(x, Direction::East, |&c| c == '#');
+        assert_eq!(hit, Some((Point::new(4, 3), &'#')));
+        assert_eq!(g.first_hit(x, Direction::West, |&c| c == '#'), None);
+
+        let until: String = g
+            .ray_until(x, Direction::East, |&c| c == '#')
+            .map(|(_, &c)| c)
+            .collect();
+        assert_eq!(until, ".#");
+        let open: String = g
+            .ray_until(x, Direction::West, |&c| c == '#')
+            .map(|(_, &c)| c)
+            .collect();
+        assert_eq!(open, "..");
+    }
+
+    #[test]
+    fn line_of_sight() {
+        let g = grid();
+        let seen: Vec<_> = g
+            .visible_from(Point::new(2, 3), |&c| c == '#')
+            .map(|(dir, p, _)| (dir, p))
+            .collect();
+        assert_eq!(
+            seen,
+            [
+                (Direction::North, Point::new(2, 1)),
+                (Direction::East, Point::new(4, 3)),
+            ]
+        );
+    }
+}
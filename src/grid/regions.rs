@@ -0,0 +1,178 @@
+//! Flood fills and connected-component labelling.
+
+use super::{Connectivity, Grid};
+
+/// One connected region found by [`Grid::components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The region's first cell in reading order.
+    pub start: (usize, usize),
+    /// Number of cells.
+    pub area: usize,
+    /// Number of cell edges facing another region or the outside.
+    pub perimeter: usize,
+    /// Top-left corner of the bounding box.
+    pub min: (usize, usize),
+    /// Bottom-right corner of the bounding box, inclusive.
+    pub max: (usize, usize),
+}
+
+/// A label per cell plus the regions the labels index into.
+#[derive(Debug, Clone)]
+pub struct Components {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T> Grid<T> {
+    /// Marks every cell 4-connected to `start` through cells matching `predicate`.
+    /// A `start` outside the grid marks nothing.
+    pub fn flood_fill(&self, start: (usize, usize), predicate: impl Fn(&T) -> bool) -> Grid<bool> {
+        self.flood([start], predicate)
+    }
+
+    /// Marks every cell 4-connected to the grid's edge through cells matching
+    /// `predicate`, e.g. everything outside a closed loop.
+    pub fn flood_from_border(&self, predicate: impl Fn(&T) -> bool) -> Grid<bool> {
+        self.flood(self.border(), predicate)
+    }
+
+    /// Coordinates on the grid's edge.
+    pub fn border(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let (w, h) = (self.width, self.height);
+        self.coords()
+            .filter(move |&(x, y)| x == 0 || y == 0 || x == w - 1 || y == h - 1)
+    }
+
+    fn flood(
+        &self,
+        starts: impl IntoIterator<Item = (usize, usize)>,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Grid<bool> {
+        let mut filled = self.map(|_| false);
+        let mut stack: Vec<(usize, usize)> = starts
+            .into_iter()
+            .filter(|&p| self.get(p).is_some_and(&predicate))
+            .collect();
+        stack.iter().for_each(|&p| filled[p] = true);
+
+        while let Some((x, y)) = stack.pop() {
+            self.neighbors_4(x, y).for_each(|(n, v)| {
+                if !filled[n] && predicate(v) {
+                    filled[n] = true;
+                    stack.push(n);
+                }
+            });
+        }
+
+        filled
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Groups connected cells with equal values into regions.
+    pub fn components(&self, connectivity: Connectivity) -> Components {
+        let mut labels = self.map(|_| usize::MAX);
+        let mut regions = Vec::new();
+
+        for start in self.coords() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = Region {
+                start,
+                area: 0,
+                perimeter: 0,
+                min: start,
+                max: start,
+            };
+            labels[start] = label;
+            let mut stack = vec![start];
+
+            while let Some((x, y)) = stack.pop() {
+                region.area += 1;
+                region.min = (region.min.0.min(x), region.min.1.min(y));
+                region.max = (region.max.0.max(x), region.max.1.max(y));
+
+                self.neighbors_with(x, y, connectivity.directions())
+                    .for_each(|(n, v)| {
+                        if labels[n] == usize::MAX && *v == self[start] {
+                            labels[n] = label;
+                            stack.push(n);
+                        }
+                    });
+            }
+
+            regions.push(region);
+        }
+
+        for (x, y) in self.coords() {
+            let label = labels[(x, y)];
+            let inner = labels
+                .neighbors_4(x, y)
+                .filter(|&(_, &l)| l == label)
+                .count();
+            regions[label].perimeter += 4 - inner;
+        }
+
+        Components { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok::<_, ()>).unwrap()
+    }
+
+    #[test]
+    fn flood_from_border_stops_at_walls() {
+        let g = grid(
+            "
+            .....
+            .###.
+            .#.#.
+            .###.
+            ",
+        );
+        let outside = g.flood_from_border(|&c| c == '.');
+        assert!(outside[(0, 0)]);
+        assert!(!outside[(2, 2)]);
+        assert!(!outside[(1, 1)]);
+        assert_eq!(outside.all().filter(|c| *c.value).count(), 11);
+
+        let inside = g.flood_fill((2, 2), |&c| c == '.');
+        assert_eq!(inside.all().filter(|c| *c.value).count(), 1);
+
+        let nowhere = g.flood_fill((9, 0), |&c| c == '.');
+        assert_eq!(nowhere.all().filter(|c| *c.value).count(), 0);
+    }
+
+    #[test]
+    fn components_report_area_perimeter_and_bounds() {
+        let g = grid(
+            "
+            AAB
+            ABB
+            CBA
+            ",
+        );
+        let four = g.components(Connectivity::Four);
+        assert_eq!(four.regions.len(), 4);
+        assert_eq!(four.labels[(0, 1)], four.labels[(0, 0)]);
+
+        let b = &four.regions[four.labels[(2, 0)]];
+        assert_eq!((b.area, b.perimeter), (4, 10));
+        assert_eq!((b.min, b.max), ((1, 0), (2, 2)));
+
+        let eight = g.components(Connectivity::Eight);
+        assert_eq!(eight.regions.len(), 4);
+        let a = &eight.regions[eight.labels[(0, 0)]];
+        assert_eq!((a.area, a.perimeter), (3, 8));
+        assert_eq!(eight.labels[(2, 2)], four.labels[(2, 2)]);
+    }
+}
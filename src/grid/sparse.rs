@@ -0,0 +1,207 @@
+//! Unbounded grids over the whole integer plane.
+
+use std::collections::HashMap;
+
+use super::Grid;
+use crate::point::{Direction, Point};
+
+/// A grid over every [`Point`], storing only cells that differ from a
+/// default value.
+///
+/// Writing the default to a cell forgets it, so [`Self::len`] and
+/// [`Self::bounds`] only see cells that differ from it. The bounding box
+/// grows and shrinks with the stored cells; unstored cells read as the
+/// default.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+}
+
+impl<T> SparseGrid<T> {
+    /// An empty grid where every cell reads as `default`.
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    /// The value at `p`, or the default if nothing is stored there.
+    pub fn get(&self, p: Point) -> &T {
+        self.cells.get(&p).unwrap_or(&self.default)
+    }
+
+    /// Forgets the value at `p`, so it reads as the default again.
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    /// Whether a value is stored at `p`.
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// Number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// The stored cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    /// Smallest and largest corner of the box around all stored cells,
+    /// both inclusive, or `None` if nothing is stored.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.cells.keys().fold(None, |bounds, &p| {
+            let (min, max) = bounds.unwrap_or((p, p));
+            Some((
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            ))
+        })
+    }
+
+    #[inline]
+    fn neighbors_with(
+        &self,
+        p: Point,
+        dirs: &'static [Direction],
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        dirs.iter().map(move |&dir| {
+            let n = p.step(dir);
+            (n, self.get(n))
+        })
+    }
+
+    /// 4-neighbors of a point (top, right, bottom, left).
+    pub fn neighbors_4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.neighbors_with(p, &Direction::ALL_4)
+    }
+
+    /// 8-neighbors of a point (including diagonals).
+    pub fn neighbors_8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.neighbors_with(p, &Direction::ALL_8)
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    /// Stores `value` at `p`, returning the previously stored value. Storing
+    /// the default removes the cell instead.
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        if value == self.default {
+            self.cells.remove(&p)
+        } else {
+            self.cells.insert(p, value)
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    /// Changes the value at `p` in place, starting from the default if
+    /// nothing is stored there. The cell is removed if `f` leaves the
+    /// default behind.
+    pub fn update<R>(&mut self, p: Point, f: impl FnOnce(&mut T) -> R) -> R {
+        let value = self.cells.entry(p).or_insert_with(|| self.default.clone());
+        let result = f(value);
+        if *value == self.default {
+            self.cells.remove(&p);
+        }
+        result
+    }
+
+    /// A dense copy of the bounding box, plus the point its `(0, 0)` maps
+    /// to. Returns `None` if nothing is stored.
+    pub fn to_grid(&self) -> Option<(Grid<T>, Point)> {
+        let (min, max) = self.bounds()?;
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+
+        let grid = Grid::from_fn(width, height, |(x, y)| {
+            self.get(min + Point::from((x, y))).clone()
+        });
+        Some((grid, min))
+    }
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Default + PartialEq> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::default();
+        iter.into_iter().for_each(|(p, v)| {
+            grid.insert(p, v);
+        });
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_default_and_tracks_bounds() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(-300_000, 5), '#');
+        grid.insert(Point::new(200_000, -7), '#');
+        assert_eq!(*grid.get(Point::new(0, 0)), '.');
+        assert_eq!(*grid.get(Point::new(-300_000, 5)), '#');
+        assert_eq!(
+            grid.bounds(),
+            Some((Point::new(-300_000, -7), Point::new(200_000, 5)))
+        );
+
+        grid.remove(Point::new(200_000, -7));
+        assert_eq!(
+            grid.bounds(),
+            Some((Point::new(-300_000, 5), Point::new(-300_000, 5)))
+        );
+    }
+
+    #[test]
+    fn neighbors_and_dense_copy() {
+        let mut grid: SparseGrid<u8> = [(Point::new(-1, -1), 1), (Point::new(1, 0), 2)]
+            .into_iter()
+            .collect();
+        grid.update(Point::new(0, 0), |v| *v += 5);
+
+        let around: Vec<u8> = grid.neighbors_8(Point::ORIGIN).map(|(_, &v)| v).collect();
+        assert_eq!(around, [1, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(grid.neighbors_4(Point::new(1, 1)).count(), 4);
+
+        let (dense, origin) = grid.to_grid().unwrap();
+        assert_eq!(origin, Point::new(-1, -1));
+        assert_eq!(dense.render(|&v| (b'0' + v) as char), "100\n052\n");
+    }
+
+    #[test]
+    fn defaults_are_not_stored() {
+        let mut grid = SparseGrid::new(0);
+        grid.insert(Point::new(0, 0), 1);
+        grid.insert(Point::new(50, 50), 0);
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid.bounds(), Some((Point::ORIGIN, Point::ORIGIN)));
+
+        grid.update(Point::new(-9, 3), |v| *v += 0);
+        grid.update(Point::new(0, 0), |v| *v -= 1);
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! Summed-area tables for constant-time rectangle sums.
+
+use std::ops::{Range, RangeInclusive};
+
+use super::Grid;
+
+/// Integer types a [`SummedArea`] can accumulate.
+pub trait Summable: Copy + Default {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_summable {
+    ($($t:ty),*) => {
+        $(impl Summable for $t {
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            #[inline]
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_summable!(u32, u64, u128, usize, i32, i64, i128);
+
+/// Prefix sums over a grid: `table[(x, y)]` holds the sum of all cells
+/// left of `x` and above `y`.
+#[derive(Debug, Clone)]
+pub struct SummedArea<S> {
+    table: Grid<S>,
+}
+
+impl<S: Summable> SummedArea<S> {
+    /// Builds the table from `value(pos, cell)` for every cell.
+    ///
+    /// Panics if the total overflows `S`.
+    pub fn new<T>(grid: &Grid<T>, mut value: impl FnMut((usize, usize), &T) -> S) -> Self {
+        let (w, h) = (grid.width(), grid.height());
+        let mut table = Grid::new(w + 1, h + 1, vec![S::default(); (w + 1) * (h + 1)]);
+
+        // A running row sum keeps every intermediate a real prefix sum, so
+        // only totals that do not fit in `S` overflow.
+        for y in 0..h {
+            let mut row = S::default();
+            for x in 0..w {
+                row = row
+                    .checked_add(value((x, y), &grid[(x, y)]))
+                    .expect("summed-area table overflow");
+                table[(x + 1, y + 1)] = table[(x + 1, y)]
+                    .checked_add(row)
+                    .expect("summed-area table overflow");
+            }
+        }
+
+        Self { table }
+    }
+
+    /// Width of the summed grid.
+    pub fn width(&self) -> usize {
+        self.table.width() - 1
+    }
+
+    /// Height of the summed grid.
+    pub fn height(&self) -> usize {
+        self.table.height() - 1
+    }
+
+    /// Sum over all cells.
+    pub fn total(&self) -> S {
+        self.table[(self.width(), self.height())]
+    }
+
+    /// Sum over the cells in `xs × ys`, or `None` on overflow.
+    pub fn sum(&self, xs: Range<usize>, ys: Range<usize>) -> Option<S> {
+        table_sum(&self.table, xs, ys)
+    }
+
+    /// Sum over the cells in `xs × ys`, both ends included.
+    pub fn sum_inclusive(&self, xs: RangeInclusive<usize>, ys: RangeInclusive<usize>) -> Option<S> {
+        self.sum(*xs.start()..xs.end() + 1, *ys.start()..ys.end() + 1)
+    }
+}
+
+fn table_sum<S: Summable>(t: &Grid<S>, xs: Range<usize>, ys: Range<usize>) -> Option<S> {
+    let bottom = t[(xs.end, ys.end)].checked_sub(t[(xs.start, ys.end)])?;
+    let top = t[(xs.end, ys.start)].checked_sub(t[(xs.start, ys.start)])?;
+    bottom.checked_sub(top)
+}
+
+impl Grid<bool> {
+    /// The `(width + 1) × (height + 1)` prefix-sum table of set cells.
+    #[deprecated(note = "use `SummedArea::new` instead")]
+    pub fn prefix_sum(&self) -> Grid<u64> {
+        SummedArea::new(self, |_, &set| u64::from(set)).table
+    }
+}
+
+impl Grid<u64> {
+    /// Sum over `x0..=x1 × y0..=y1`, reading `self` as a table from
+    /// [`Grid::prefix_sum`].
+    #[deprecated(note = "use `SummedArea::sum_inclusive` instead")]
+    #[inline]
+    pub fn rect_sum(&self, x0: usize, y0: usize, x1: usize, y1: usize) -> u64 {
+        table_sum(self, x0..x1 + 1, y0..y1 + 1).expect("not a prefix-sum table")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle_sums() {
+        let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]);
+        let sums = SummedArea::new(&grid, |_, &v| v as u64);
+        assert_eq!(sums.total(), 21);
+        assert_eq!(sums.sum(1..3, 0..2), Some(16));
+        assert_eq!(sums.sum(1..1, 0..2), Some(0));
+        assert_eq!(sums.sum_inclusive(0..=0, 1..=1), Some(4));
+    }
+
+    #[test]
+    fn weighted_and_signed() {
+        let grid = Grid::new(2, 2, vec![true, false, false, true]);
+        let weights = SummedArea::new(&grid, |(x, y), &on| {
+            if on {
+                (x + 1) as u128 * 10u128.pow(y as u32 + 30)
+            } else {
+                0
+            }
+        });
+        assert_eq!(
+            weights.sum_inclusive(1..=1, 1..=1),
+            Some(2 * 10u128.pow(31))
+        );
+
+        let signed = SummedArea::new(&grid, |_, &on| if on { 1i64 } else { -1 });
+        assert_eq!(signed.total(), 0);
+        assert_eq!(signed.sum(0..2, 0..1), Some(0));
+        assert_eq!(signed.sum(1..2, 0..2), Some(0));
+        assert_eq!(signed.sum(1..2, 0..1), Some(-1));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_wrappers() {
+        let grid = Grid::new(2, 2, vec![true, true, false, true]);
+        let table = grid.prefix_sum();
+        assert_eq!(table.rect_sum(0, 0, 1, 1), 3);
+        assert_eq!(table.rect_sum(0, 1, 0, 1), 0);
+    }
+
+    #[test]
+    fn large_values_without_overflow() {
+        let big = u32::MAX / 2 + 1;
+        let grid = Grid::new(2, 2, vec![big, 0, 0, 0]);
+        let sums = SummedArea::new(&grid, |_, &v| v);
+        assert_eq!(sums.total(), big);
+        assert_eq!(sums.sum(1..2, 1..2), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn overflow_is_detected() {
+        let grid = Grid::new(2, 1, vec![u32::MAX, 1]);
+        SummedArea::new(&grid, |_, &v| v);
+    }
+}
@@ -0,0 +1,64 @@
+//! Rotations, flips and transposition, each producing a new grid.
+
+use super::Grid;
+
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns: `(x, y)` moves to `(y, x)`.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let h = self.height;
+        Self::from_fn(h, self.width, |(x, y)| self[(y, h - 1 - x)].clone())
+    }
+
+    /// Rotates a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let w = self.width;
+        Self::from_fn(self.height, w, |(x, y)| self[(w - 1 - y, x)].clone())
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_h(&self) -> Self {
+        let w = self.width;
+        Self::from_fn(w, self.height, |(x, y)| self[(w - 1 - x, y)].clone())
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_v(&self) -> Self {
+        let h = self.height;
+        Self::from_fn(self.width, h, |(x, y)| self[(x, h - 1 - y)].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok::<_, ()>).unwrap()
+    }
+
+    #[test]
+    fn transforms() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose().render(|&c| c), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_cw().render(|&c| c), "da\neb\nfc\n");
+        assert_eq!(g.rotate_ccw().render(|&c| c), "cf\nbe\nad\n");
+        assert_eq!(g.flip_h().render(|&c| c), "cba\nfed\n");
+        assert_eq!(g.flip_v().render(|&c| c), "def\nabc\n");
+    }
+
+    #[test]
+    fn four_turns_are_identity() {
+        let g = grid("ab\ncd\nef");
+        let turned = g.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+        assert_eq!(turned.render(|&c| c), g.render(|&c| c));
+        assert_eq!(
+            g.rotate_cw().render(|&c| c),
+            g.transpose().flip_h().render(|&c| c)
+        );
+    }
+}
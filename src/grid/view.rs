@@ -0,0 +1,128 @@
+//! Borrowed rectangular views into a grid.
+
+use std::ops::{Index, Range};
+
+use super::{Grid, GridPos};
+
+/// A rectangular window into a [`Grid`], addressed from its own top-left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'g, T> {
+    grid: &'g Grid<T>,
+    x0: usize,
+    y0: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A view on the cells in `xs × ys`.
+    ///
+    /// Panics if the ranges reach outside the grid.
+    pub fn view(&self, xs: Range<usize>, ys: Range<usize>) -> GridView<'_, T> {
+        assert!(
+            xs.start <= xs.end && xs.end <= self.width,
+            "columns {xs:?} are outside the grid"
+        );
+        assert!(
+            ys.start <= ys.end && ys.end <= self.height,
+            "rows {ys:?} are outside the grid"
+        );
+
+        GridView {
+            grid: self,
+            x0: xs.start,
+            y0: ys.start,
+            width: xs.len(),
+            height: ys.len(),
+        }
+    }
+}
+
+impl<'g, T> GridView<'g, T> {
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, pos: impl GridPos) -> Option<&'g T> {
+        let (x, y) = pos.xy()?;
+        if x < self.width && y < self.height {
+            self.grid.get((self.x0 + x, self.y0 + y))
+        } else {
+            None
+        }
+    }
+
+    /// The cells of row `y` within the view.
+    pub fn row(&self, y: usize) -> &'g [T] {
+        assert!(y < self.height, "row {y} is outside the view");
+        &self.grid.row(self.y0 + y)[self.x0..self.x0 + self.width]
+    }
+
+    /// All rows of the view, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &'g [T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// The cells of column `x` within the view, top to bottom.
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &'g T> + '_ {
+        assert!(x < self.width, "column {x} is outside the view");
+        (0..self.height).map(move |y| &self.grid[(self.x0 + x, self.y0 + y)])
+    }
+
+    /// All coordinates of the view, relative to its top-left corner.
+    pub fn coords(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+    }
+
+    /// Copies the view into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |p| self[p].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the view"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_and_cols() {
+        let g = Grid::parse("abc\ndef", Ok::<_, ()>).unwrap();
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(g.col(2).collect::<String>(), "cf");
+        assert_eq!(
+            g.cols().map(|c| c.collect::<String>()).collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(g.rows().count(), 2);
+    }
+
+    #[test]
+    fn views_are_offset() {
+        let g = Grid::parse("abcd\nefgh\nijkl", Ok::<_, ()>).unwrap();
+        let v = g.view(1..3, 1..3);
+        assert_eq!((v.width(), v.height()), (2, 2));
+        assert_eq!(v[(0, 0)], 'f');
+        assert_eq!(v.get((2, 0)), None);
+        assert_eq!(v.row(1), ['j', 'k']);
+        assert_eq!(v.col(1).collect::<String>(), "gk");
+        assert_eq!(v.to_grid().render(|&c| c), "fg\njk\n");
+    }
+}
@@ -0,0 +1,254 @@
+//! Dense 3D grids for voxel and cube puzzles.
+
+use std::ops::{Index, IndexMut};
+
+use crate::grid::Grid;
+use crate::point::Point3;
+
+/// One of the three axes of a [`Grid3`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// A `width × height × depth` box of cells, stored layer by layer.
+#[derive(Debug, Clone)]
+pub struct Grid3<T> {
+    width: usize,
+    height: usize,
+    depth: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid3<T> {
+    pub fn new(width: usize, height: usize, depth: usize, data: Vec<T>) -> Self {
+        assert_eq!(width * height * depth, data.len());
+        Self {
+            width,
+            height,
+            depth,
+            data,
+        }
+    }
+
+    /// Builds a grid by calling `f` for every point, `x` varying fastest.
+    pub fn from_fn(width: usize, height: usize, depth: usize, f: impl FnMut(Point3) -> T) -> Self {
+        let data = Self::points(width, height, depth).map(f).collect();
+        Self::new(width, height, depth, data)
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    #[inline]
+    fn index_of(&self, p: Point3) -> Option<usize> {
+        let (x, y, z) = <(usize, usize, usize)>::try_from(p).ok()?;
+        (x < self.width && y < self.height && z < self.depth)
+            .then(|| (z * self.height + y) * self.width + x)
+    }
+
+    /// Whether `p` lies inside the grid.
+    pub fn contains(&self, p: Point3) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    pub fn get(&self, p: Point3) -> Option<&T> {
+        self.index_of(p).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point3) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.data[i])
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid3<U> {
+        Grid3::new(
+            self.width,
+            self.height,
+            self.depth,
+            self.data.iter().map(f).collect(),
+        )
+    }
+
+    fn points(width: usize, height: usize, depth: usize) -> impl Iterator<Item = Point3> {
+        (0..depth).flat_map(move |z| {
+            (0..height).flat_map(move |y| (0..width).map(move |x| Point3::from((x, y, z))))
+        })
+    }
+
+    /// All points of the grid, `x` varying fastest.
+    pub fn coords(&self) -> impl Iterator<Item = Point3> + '_ {
+        Self::points(self.width, self.height, self.depth)
+    }
+
+    #[inline]
+    fn neighbors_with<'a>(
+        &'a self,
+        p: Point3,
+        offsets: &'static [Point3],
+    ) -> impl Iterator<Item = (Point3, &'a T)> + 'a {
+        offsets.iter().filter_map(move |&d| {
+            let n = p + d;
+            self.get(n).map(|v| (n, v))
+        })
+    }
+
+    /// Face-adjacent neighbors of `p`.
+    pub fn neighbors_6(&self, p: Point3) -> impl Iterator<Item = (Point3, &T)> + '_ {
+        self.neighbors_with(p, &Point3::ADJACENT_6)
+    }
+
+    /// Neighbors of `p` sharing a face, edge or corner.
+    pub fn neighbors_26(&self, p: Point3) -> impl Iterator<Item = (Point3, &T)> + '_ {
+        self.neighbors_with(p, &Point3::ADJACENT_26)
+    }
+
+    /// Marks every cell face-connected to `start` through cells matching `predicate`.
+    /// A `start` outside the grid marks nothing.
+    pub fn flood_fill(&self, start: Point3, predicate: impl Fn(&T) -> bool) -> Grid3<bool> {
+        self.flood([start], predicate)
+    }
+
+    /// Marks every cell face-connected to the grid's surface through cells
+    /// matching `predicate`, e.g. the air around a droplet.
+    pub fn flood_from_border(&self, predicate: impl Fn(&T) -> bool) -> Grid3<bool> {
+        let (w, h, d) = (self.width as i64, self.height as i64, self.depth as i64);
+        let surface = self.coords().filter(|p| {
+            p.x == 0 || p.y == 0 || p.z == 0 || p.x == w - 1 || p.y == h - 1 || p.z == d - 1
+        });
+        self.flood(surface, predicate)
+    }
+
+    fn flood(
+        &self,
+        starts: impl IntoIterator<Item = Point3>,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Grid3<bool> {
+        let mut filled = self.map(|_| false);
+        let mut stack: Vec<Point3> = starts
+            .into_iter()
+            .filter(|&p| self.get(p).is_some_and(&predicate))
+            .collect();
+        stack.iter().for_each(|&p| filled[p] = true);
+
+        while let Some(p) = stack.pop() {
+            self.neighbors_6(p).for_each(|(n, v)| {
+                if !filled[n] && predicate(v) {
+                    filled[n] = true;
+                    stack.push(n);
+                }
+            });
+        }
+
+        filled
+    }
+}
+
+impl<T: Clone> Grid3<T> {
+    /// The 2D layer where `axis` equals `index`.
+    ///
+    /// The remaining axes keep their order: a `Z` slice is indexed by
+    /// `(x, y)`, a `Y` slice by `(x, z)` and an `X` slice by `(y, z)`.
+    pub fn slice(&self, axis: Axis, index: usize) -> Grid<T> {
+        let i = index as i64;
+        let at = |a: usize, b: usize| {
+            let (a, b) = (a as i64, b as i64);
+            match axis {
+                Axis::X => Point3::new(i, a, b),
+                Axis::Y => Point3::new(a, i, b),
+                Axis::Z => Point3::new(a, b, i),
+            }
+        };
+        let (w, h) = match axis {
+            Axis::X => (self.height, self.depth),
+            Axis::Y => (self.width, self.depth),
+            Axis::Z => (self.width, self.height),
+        };
+
+        Grid::from_fn(w, h, |(a, b)| self[at(a, b)].clone())
+    }
+}
+
+impl<T> Index<Point3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, p: Point3) -> &Self::Output {
+        self.get(p)
+            .unwrap_or_else(|| panic!("point {p:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point3> for Grid3<T> {
+    fn index_mut(&mut self, p: Point3) -> &mut Self::Output {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("point {p:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A hollow 3×3×3 cube inside a 5×5×5 grid.
+    fn shell() -> Grid3<bool> {
+        Grid3::from_fn(5, 5, 5, |p| {
+            let inside = |v: i64| (1..=3).contains(&v);
+            inside(p.x) && inside(p.y) && inside(p.z) && p != Point3::new(2, 2, 2)
+        })
+    }
+
+    #[test]
+    fn neighbors_clip_at_faces() {
+        let g = shell();
+        assert_eq!(g.neighbors_6(Point3::ORIGIN).count(), 3);
+        assert_eq!(g.neighbors_26(Point3::ORIGIN).count(), 7);
+        assert_eq!(g.neighbors_26(Point3::new(2, 2, 2)).count(), 26);
+        assert_eq!(
+            g.neighbors_6(Point3::new(2, 2, 2))
+                .filter(|(_, v)| **v)
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn flood_does_not_enter_the_shell() {
+        let g = shell();
+        let air = g.flood_from_border(|&solid| !solid);
+        assert_eq!(air.coords().filter(|&p| air[p]).count(), 125 - 27);
+        assert!(!air[Point3::new(2, 2, 2)]);
+
+        let cavity = g.flood_fill(Point3::new(2, 2, 2), |&solid| !solid);
+        assert_eq!(cavity.coords().filter(|&p| cavity[p]).count(), 1);
+    }
+
+    #[test]
+    fn slices() {
+        let g = Grid3::from_fn(2, 3, 4, |p| p.x * 100 + p.y * 10 + p.z);
+        let z = g.slice(Axis::Z, 3);
+        assert_eq!((z.width(), z.height()), (2, 3));
+        assert_eq!(z[(1, 2)], 123);
+
+        let y = g.slice(Axis::Y, 1);
+        assert_eq!((y.width(), y.height()), (2, 4));
+        assert_eq!(y[(1, 3)], 113);
+
+        let x = g.slice(Axis::X, 0);
+        assert_eq!((x.width(), x.height()), (3, 4));
+        assert_eq!(x[(2, 1)], 21);
+    }
+}
@@ -0,0 +1,289 @@
+//! Rendering grids to images, for watching what a simulation does.
+//!
+//! PPM output has no dependencies. PNG and animated GIF output need the
+//! `png` and `gif` features.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::grid::Grid;
+
+/// An 8-bit RGB colour.
+pub type Rgb = [u8; 3];
+
+/// An RGB raster, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Paints every cell of `grid` as a `scale × scale` square of `colour(cell)`.
+    pub fn from_grid<T>(grid: &Grid<T>, scale: usize, mut colour: impl FnMut(&T) -> Rgb) -> Self {
+        let (width, height) = (grid.width() * scale, grid.height() * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for row in grid.rows() {
+            let line: Vec<Rgb> = row
+                .iter()
+                .flat_map(|cell| std::iter::repeat_n(colour(cell), scale))
+                .collect();
+            (0..scale).for_each(|_| pixels.extend_from_slice(&line));
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The colour at pixel `(x, y)`.
+    pub fn pixel(&self, (x, y): (usize, usize)) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.concat()
+    }
+
+    /// Encodes as binary PPM (`P6`).
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.bytes())
+    }
+
+    /// Encodes as an RGB PNG.
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.bytes())
+            .map_err(io::Error::other)
+    }
+
+    /// Writes to `path`, picking the format from its extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = Format::of(path)?;
+        let mut out = BufWriter::new(File::create(path)?);
+
+        match format {
+            Format::Ppm => self.write_ppm(&mut out)?,
+            #[cfg(feature = "png")]
+            Format::Png => self.write_png(&mut out)?,
+        }
+        out.flush()
+    }
+}
+
+/// Still-image formats [`Image::save`] understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            #[cfg(feature = "png")]
+            Format::Png => "png",
+        }
+    }
+
+    fn of(path: &Path) -> io::Result<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => Ok(Format::Ppm),
+            #[cfg(feature = "png")]
+            Some("png") => Ok(Format::Png),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported image format: {}", path.display()),
+            )),
+        }
+    }
+}
+
+/// Collects one image per simulation step.
+#[derive(Debug, Clone)]
+pub struct FrameRecorder<F> {
+    scale: usize,
+    colour: F,
+    frames: Vec<Image>,
+}
+
+impl<F> FrameRecorder<F> {
+    /// Records grids with `scale` pixels per cell, coloured by `colour`.
+    pub fn new(scale: usize, colour: F) -> Self {
+        Self {
+            scale,
+            colour,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Renders `grid` as the next frame.
+    pub fn record<T>(&mut self, grid: &Grid<T>)
+    where
+        F: FnMut(&T) -> Rgb,
+    {
+        let frame = Image::from_grid(grid, self.scale, &mut self.colour);
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// Writes `dir/frame_0000.<ext>`, `dir/frame_0001.<ext>`, …, creating
+    /// `dir` if needed.
+    pub fn write_sequence(&self, dir: impl AsRef<Path>, format: Format) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        self.frames.iter().enumerate().try_for_each(|(i, frame)| {
+            frame.save(dir.join(format!("frame_{i:04}.{}", format.extension())))
+        })
+    }
+
+    /// Writes all frames as a looping GIF, `delay` hundredths of a second
+    /// apart. Frames must all have the same size.
+    #[cfg(feature = "gif")]
+    pub fn write_gif(&self, out: impl Write, delay: u16) -> io::Result<()> {
+        let Some(first) = self.frames.first() else {
+            return Ok(());
+        };
+        let size =
+            |n: usize| u16::try_from(n).map_err(|_| io::Error::other("frame too large for a GIF"));
+        let (width, height) = (size(first.width)?, size(first.height)?);
+
+        let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        for image in &self.frames {
+            if (image.width, image.height) != (first.width, first.height) {
+                return Err(io::Error::other("GIF frames differ in size"));
+            }
+            let mut frame = gif::Frame::from_rgb(width, height, &image.bytes());
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = [0, 0, 0];
+    const WHITE: Rgb = [255, 255, 255];
+
+    fn colour(&on: &bool) -> Rgb {
+        if on { WHITE } else { BLACK }
+    }
+
+    #[test]
+    fn scales_cells() {
+        let grid = Grid::new(2, 1, vec![true, false]);
+        let image = Image::from_grid(&grid, 2, colour);
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.pixel((1, 1)), Some(WHITE));
+        assert_eq!(image.pixel((2, 0)), Some(BLACK));
+        assert_eq!(image.pixel((4, 0)), None);
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let grid = Grid::new(2, 1, vec![false, true]);
+        let mut out = Vec::new();
+        Image::from_grid(&grid, 1, colour)
+            .write_ppm(&mut out)
+            .unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\0\0\0\xff\xff\xff");
+    }
+
+    #[test]
+    fn records_frames() {
+        let mut recorder = FrameRecorder::new(1, colour);
+        let mut grid = Grid::new(2, 2, vec![false; 4]);
+        recorder.record(&grid);
+        grid[(1, 1)] = true;
+        recorder.record(&grid);
+
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].pixel((1, 1)), Some(BLACK));
+        assert_eq!(frames[1].pixel((1, 1)), Some(WHITE));
+    }
+
+    #[test]
+    fn unsupported_format_writes_nothing() {
+        let path = std::env::temp_dir().join(format!("aoc_image_{}.bmp", std::process::id()));
+        let image = Image::from_grid(&Grid::new(1, 1, vec![true]), 1, colour);
+
+        let err = image.save(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(!path.exists());
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn encodes_png() {
+        let grid = Grid::new(2, 1, vec![false, true]);
+        let mut out = Vec::new();
+        Image::from_grid(&grid, 1, colour)
+            .write_png(&mut out)
+            .unwrap();
+
+        let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(&pixels[..info.buffer_size()], [0, 0, 0, 255, 255, 255]);
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn encodes_gif() {
+        let mut recorder = FrameRecorder::new(1, colour);
+        recorder.record(&Grid::new(2, 1, vec![false, true]));
+        recorder.record(&Grid::new(2, 1, vec![true, true]));
+        let mut out = Vec::new();
+        recorder.write_gif(&mut out, 10).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (2, 1));
+
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push(frame.buffer.to_vec());
+        }
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0], [0, 0, 0, 255, 255, 255, 255, 255]);
+        assert_eq!(frames[1], [255; 8]);
+    }
+}
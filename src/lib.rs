@@ -3,9 +3,22 @@
 //! This module provides common utilities such as reading puzzle inputs,
 //! trimming lines, and structuring a typical AoC workflow (parse → solve).
 
-use std::fs;
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
+pub mod disjoint_set;
+pub mod geometry;
 pub mod grid;
+pub mod grid3;
+pub mod image;
+pub mod mst;
+pub mod point;
+pub mod search;
+pub mod spatial;
 
 /// Reads an input file into a trimmed string.
 pub fn read_input(path: &str) -> String {
@@ -20,17 +33,257 @@ pub fn lines(input: &str) -> impl Iterator<Item = &str> {
     input.lines().map(str::trim).filter(|l| !l.is_empty())
 }
 
+/// Tunable puzzle parameters, e.g. how many connections to make or which
+/// node to start from.
+///
+/// The real puzzle and its worked example often differ only in such numbers,
+/// so both variants live here instead of behind `#[cfg(test)]`.
+pub trait DayParams: Sized {
+    /// Parameters of the real puzzle.
+    fn puzzle() -> Self;
+
+    /// Parameters of the worked example from the puzzle text.
+    fn example() -> Self {
+        Self::puzzle()
+    }
+
+    /// Overrides a single parameter from a `key=value` pair.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+impl DayParams for () {
+    fn puzzle() -> Self {}
+
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), String> {
+        Err(format!("unknown parameter: {key}"))
+    }
+}
+
+/// Parses a parameter value, naming the key on failure.
+pub fn parse_param<T>(key: &str, value: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| format!("invalid value for {key}: {value} ({e})"))
+}
+
 /// A template for all puzzle days:
 /// 1. parse the input
 /// 2. compute part 1
 /// 3. compute part 2
 ///
-/// Not required to use, but a handy pattern.
+/// Implement it and let [`aoc_main!`] generate the binary's `main`.
 pub trait AoCDay {
-    type Parsed;
+    /// The puzzle day, used for the default input path and the banner.
+    const DAY: u8;
+
+    /// The parsed input. It may borrow from the input text, e.g.
+    /// `HashMap<&'a str, Vec<&'a str>>`; owned data simply ignores `'a`.
+    type Parsed<'a>;
+
+    /// Use `()` for days without tunable parameters.
+    type Params: DayParams;
+
+    fn parse(input: &str) -> Self::Parsed<'_>;
+
+    fn part1(data: &Self::Parsed<'_>, params: &Self::Params) -> String;
+    fn part2(data: &Self::Parsed<'_>, params: &Self::Params) -> String;
+
+    /// Solves both parts at once.
+    ///
+    /// Override it when both parts share expensive work, e.g. sorting all
+    /// pairwise distances, so that work is done only once.
+    fn solve(data: &Self::Parsed<'_>, params: &Self::Params) -> (String, String) {
+        (Self::part1(data, params), Self::part2(data, params))
+    }
+}
+
+/// A day whose input lines are independent of each other.
+///
+/// Instead of loading and parsing the whole input, each non-empty, trimmed
+/// line is folded into an accumulator, so arbitrarily large inputs run in
+/// constant memory.
+pub trait StreamingDay: AoCDay {
+    type Acc;
+
+    fn init(params: &Self::Params) -> Self::Acc;
+
+    fn fold_line(acc: Self::Acc, line: &str, params: &Self::Params) -> Self::Acc;
+
+    fn finish(acc: Self::Acc, params: &Self::Params) -> (String, String);
+}
+
+/// Solves both parts of a [`StreamingDay`] line by line from `reader`.
+pub fn solve_stream<D: StreamingDay>(
+    mut reader: impl BufRead,
+    params: &D::Params,
+) -> io::Result<(String, String)> {
+    let mut acc = D::init(params);
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            acc = D::fold_line(acc, trimmed, params);
+        }
+        line.clear();
+    }
+
+    Ok(D::finish(acc, params))
+}
+
+/// Runs a day end to end: read → parse → solve, timing each phase.
+///
+/// Allows `cargo run --bin dayXX` or an explicit input:
+/// `cargo run --bin dayXX -- foo.txt`.
+///
+/// `--example` switches to the example parameters and
+/// `--param key=value` overrides single ones, e.g.
+/// `cargo run --bin day08 -- sample.txt --example --param connections=5`.
+pub fn run<D: AoCDay>() {
+    let (input_path, params) = args::<D>();
+    let raw = read_input(&input_path);
+
+    println!("🎄 Day {:02} — Advent of Code 2025", D::DAY);
+
+    let (parsed, parse_time) = timed(|| D::parse(&raw));
+    let ((part1, part2), solve_time) = timed(|| D::solve(&parsed, &params));
+
+    println!("⭐ Part 1: {part1}");
+    println!("⭐ Part 2: {part2}");
+    println!("⏱️ Parse: {parse_time:?}, Solve: {solve_time:?}");
+}
+
+/// Like [`run`], but streams the input through [`StreamingDay`].
+pub fn run_streaming<D: StreamingDay>() {
+    let (input_path, params) = args::<D>();
+    let file = File::open(&input_path).expect("Failed to read input file");
+
+    println!("🎄 Day {:02} — Advent of Code 2025", D::DAY);
+
+    let (answers, stream_time) = timed(|| solve_stream::<D>(BufReader::new(file), &params));
+    let (part1, part2) = answers.expect("Failed to read input file");
+
+    println!("⭐ Part 1: {part1}");
+    println!("⭐ Part 2: {part2}");
+    println!("⏱️ Stream: {stream_time:?}");
+}
+
+/// Input path and parameters from the command line.
+fn args<D: AoCDay>() -> (String, D::Params) {
+    let mut input_path = format!("inputs/day{:02}.txt", D::DAY);
+    let mut example = false;
+    let mut overrides = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--example" => example = true,
+            "--param" => overrides.push(args.next().expect("--param needs key=value")),
+            flag if flag.starts_with("--") => panic!("unknown option: {flag}"),
+            _ => input_path = arg,
+        }
+    }
+
+    let mut params = if example {
+        D::Params::example()
+    } else {
+        D::Params::puzzle()
+    };
+    overrides.iter().for_each(|kv| {
+        let (key, value) = kv.split_once('=').expect("--param needs key=value");
+        params.set(key, value).unwrap_or_else(|e| panic!("{e}"));
+    });
+
+    (input_path, params)
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Generates `fn main` for a day binary.
+///
+/// Add `stream` to read the input line by line through [`StreamingDay`]:
+///
+/// ```ignore
+/// aoc_main!(Day01);
+/// aoc_main!(Day01, stream);
+/// ```
+#[macro_export]
+macro_rules! aoc_main {
+    ($day:ty) => {
+        fn main() {
+            $crate::run::<$day>();
+        }
+    };
+    ($day:ty, stream) => {
+        fn main() {
+            $crate::run_streaming::<$day>();
+        }
+    };
+}
+
+/// Generates `test_part1`, `test_part2` and `test_solve` from
+/// `(sample, part1, part2)` tuples.
+///
+/// Use `_` for a part the sample does not cover. Samples run with
+/// [`DayParams::example`] unless a fourth element supplies the parameters:
+///
+/// ```ignore
+/// aoc_tests!(Day11, [
+///     (SAMPLE1, "5", _),
+///     (SAMPLE2, _, "2"),
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! aoc_tests {
+    (@expect $actual:ident, _) => {
+        let _ = $actual;
+    };
+    (@expect $actual:ident, $expected:tt) => {
+        assert_eq!($actual, $expected);
+    };
+    (@check $day:ty, $part:ident, $sample:expr, $expected:tt) => {
+        $crate::aoc_tests!(
+            @check $day,
+            $part,
+            $sample,
+            $expected,
+            <<$day as $crate::AoCDay>::Params as $crate::DayParams>::example()
+        );
+    };
+    (@check $day:ty, solve, $sample:expr, ($part1:tt, $part2:tt), $params:expr) => {
+        let parsed = <$day as $crate::AoCDay>::parse($sample);
+        let (part1, part2) = <$day as $crate::AoCDay>::solve(&parsed, &$params);
+        $crate::aoc_tests!(@expect part1, $part1);
+        $crate::aoc_tests!(@expect part2, $part2);
+    };
+    (@check $day:ty, $part:ident, $sample:expr, _, $params:expr) => {};
+    (@check $day:ty, $part:ident, $sample:expr, $expected:tt, $params:expr) => {
+        let parsed = <$day as $crate::AoCDay>::parse($sample);
+        let actual = <$day as $crate::AoCDay>::$part(&parsed, &$params);
+        $crate::aoc_tests!(@expect actual, $expected);
+    };
+    ($day:ty, [$(($sample:expr, $part1:tt, $part2:tt $(, $params:expr)?)),+ $(,)?]) => {
+        #[test]
+        fn test_part1() {
+            $($crate::aoc_tests!(@check $day, part1, $sample, $part1 $(, $params)?);)+
+        }
 
-    fn parse(input: &str) -> Self::Parsed;
+        #[test]
+        fn test_part2() {
+            $($crate::aoc_tests!(@check $day, part2, $sample, $part2 $(, $params)?);)+
+        }
 
-    fn part1(data: &Self::Parsed) -> String;
-    fn part2(data: &Self::Parsed) -> String;
+        #[test]
+        fn test_solve() {
+            $($crate::aoc_tests!(@check $day, solve, $sample, ($part1, $part2) $(, $params)?);)+
+        }
+    };
 }
@@ -0,0 +1,208 @@
+//! Minimum spanning trees and single-linkage clustering over point sets.
+//!
+//! Points are anything with a metric `Fn(&P, &P) -> D`; only the order of
+//! distances matters, so squared distances work as well as real ones.
+
+use crate::disjoint_set::DisjointSet;
+
+/// A connection between points `a` and `b`, indices into the point set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge<D> {
+    pub a: usize,
+    pub b: usize,
+    pub weight: D,
+}
+
+/// Every pair of points, shortest first. Equal distances keep the order of
+/// `(a, b)` with `a < b`.
+pub fn sorted_pairs<P, D: Ord>(points: &[P], metric: impl Fn(&P, &P) -> D) -> Vec<Edge<D>> {
+    let mut pairs: Vec<Edge<D>> = (0..points.len())
+        .flat_map(|a| (a + 1..points.len()).map(move |b| (a, b)))
+        .map(|(a, b)| Edge {
+            a,
+            b,
+            weight: metric(&points[a], &points[b]),
+        })
+        .collect();
+    pairs.sort_by(|e, f| e.weight.cmp(&f.weight));
+    pairs
+}
+
+/// The minimum spanning tree edges by Prim's algorithm, in the order they
+/// join the tree.
+///
+/// Takes `O(n²)` time but only `O(n)` memory, as it never lists the pairs.
+pub fn prim<P, D: Ord + Copy>(points: &[P], metric: impl Fn(&P, &P) -> D) -> Vec<Edge<D>> {
+    let n = points.len();
+    let mut in_tree = vec![false; n];
+    // For each point outside the tree, its closest tree point and distance.
+    let mut closest: Vec<Option<(usize, D)>> = vec![None; n];
+    let mut edges = Vec::with_capacity(n.saturating_sub(1));
+
+    let mut next = (n > 0).then_some(0);
+    while let Some(v) = next {
+        in_tree[v] = true;
+        if let Some((u, weight)) = closest[v] {
+            edges.push(Edge { a: u, b: v, weight });
+        }
+
+        next = None;
+        for w in (0..n).filter(|&w| !in_tree[w]) {
+            let d = metric(&points[v], &points[w]);
+            if closest[w].is_none_or(|(_, best)| d < best) {
+                closest[w] = Some((v, d));
+            }
+            if next.is_none_or(|x: usize| closest[w].unwrap().1 < closest[x].unwrap().1) {
+                next = Some(w);
+            }
+        }
+    }
+
+    edges
+}
+
+/// A minimum spanning forest built by Kruskal's algorithm, remembering when
+/// each edge was accepted so clustering queries can be answered afterwards.
+#[derive(Debug, Clone)]
+pub struct SpanningForest<D> {
+    len: usize,
+    edges: Vec<Edge<D>>,
+    /// Position of each accepted edge among all candidate edges.
+    ranks: Vec<usize>,
+}
+
+impl<D: Ord + Copy> SpanningForest<D> {
+    /// Runs Kruskal over `len` points and candidate `edges`, which must come
+    /// shortest first. Stops reading edges once everything is connected.
+    pub fn kruskal(len: usize, edges: impl IntoIterator<Item = Edge<D>>) -> Self {
+        let mut sets = DisjointSet::new(len);
+        let mut forest = Self {
+            len,
+            edges: Vec::with_capacity(len.saturating_sub(1)),
+            ranks: Vec::with_capacity(len.saturating_sub(1)),
+        };
+
+        for (rank, edge) in edges.into_iter().enumerate() {
+            if sets.components() <= 1 {
+                break;
+            }
+            if sets.union(edge.a, edge.b) {
+                forest.edges.push(edge);
+                forest.ranks.push(rank);
+            }
+        }
+        forest
+    }
+
+    /// The minimum spanning tree of `points` under `metric`.
+    pub fn of_points<P>(points: &[P], metric: impl Fn(&P, &P) -> D) -> Self {
+        Self::kruskal(points.len(), sorted_pairs(points, metric))
+    }
+
+    /// Number of points.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The accepted edges, shortest first.
+    pub fn edges(&self) -> &[Edge<D>] {
+        &self.edges
+    }
+
+    /// Total weight of the forest.
+    pub fn weight(&self) -> D
+    where
+        D: std::iter::Sum,
+    {
+        self.edges.iter().map(|e| e.weight).sum()
+    }
+
+    /// Whether the candidate edges joined all points into one tree.
+    pub fn is_connected(&self) -> bool {
+        self.edges.len() + 1 >= self.len
+    }
+
+    /// The edge that joined the last two components, if the points became
+    /// connected and there were at least two of them.
+    pub fn connecting_edge(&self) -> Option<&Edge<D>> {
+        self.edges.last().filter(|_| self.is_connected())
+    }
+
+    /// The clusters formed by the `k` shortest candidate edges, whether or
+    /// not each of them merged anything.
+    pub fn clusters_after(&self, k: usize) -> DisjointSet {
+        self.clusters(|i| self.ranks[i] < k)
+    }
+
+    /// The clusters formed by all edges of weight at most `threshold`.
+    pub fn clusters_within(&self, threshold: D) -> DisjointSet {
+        self.clusters(|i| self.edges[i].weight <= threshold)
+    }
+
+    /// Clusters from the longest prefix of accepted edges matching `keep`.
+    fn clusters(&self, keep: impl Fn(usize) -> bool) -> DisjointSet {
+        let mut sets = DisjointSet::new(self.len);
+        for i in (0..self.edges.len()).take_while(|&i| keep(i)) {
+            sets.union(self.edges[i].a, self.edges[i].b);
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    /// Points on a line: two tight clusters far apart, and a loner.
+    const POINTS: [i64; 6] = [0, 1, 3, 20, 22, 50];
+
+    fn gap(a: &i64, b: &i64) -> i64 {
+        (a - b).abs()
+    }
+
+    #[test]
+    fn kruskal_and_prim_agree() {
+        let forest = SpanningForest::of_points(&POINTS, gap);
+        assert_eq!(
+            forest.edges().iter().map(|e| e.weight).collect_vec(),
+            [1, 2, 2, 17, 28]
+        );
+        assert_eq!(forest.weight(), 50);
+
+        let tree = prim(&POINTS, gap);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.iter().map(|e| e.weight).sum::<i64>(), 50);
+    }
+
+    #[test]
+    fn clustering_queries() {
+        let forest = SpanningForest::of_points(&POINTS, gap);
+        let sizes = |sets: DisjointSet| sets.sizes().sorted().rev().collect_vec();
+
+        // The 4th shortest pair, (0, 2), merges nothing new.
+        assert_eq!(sizes(forest.clusters_after(3)), [3, 2, 1]);
+        assert_eq!(sizes(forest.clusters_after(4)), [3, 2, 1]);
+        assert_eq!(sizes(forest.clusters_within(2)), [3, 2, 1]);
+        assert_eq!(sizes(forest.clusters_within(17)), [5, 1]);
+
+        let last = forest.connecting_edge().unwrap();
+        assert_eq!((last.a, last.b, last.weight), (4, 5, 28));
+    }
+
+    #[test]
+    fn disconnected_candidates() {
+        let edges = [Edge {
+            a: 0,
+            b: 1,
+            weight: 1,
+        }];
+        let forest = SpanningForest::kruskal(3, edges);
+        assert!(!forest.is_connected());
+        assert_eq!(forest.connecting_edge(), None);
+    }
+}
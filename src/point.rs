@@ -0,0 +1,386 @@
+//! Signed 2D and 3D points, and compass directions for grid navigation.
+//!
+//! `y` grows downwards, matching how puzzle grids are printed, so
+//! [`Direction::North`] is `(0, -1)`.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed 2D point; also used as the offset between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Taxicab distance: steps needed when moving in 4 directions.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// King's distance: steps needed when moving in 8 directions.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Moves one step in `dir`.
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.offset()
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as i64, y as i64)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl TryFrom<Point> for (usize, usize) {
+    type Error = Point;
+
+    /// Fails for points with a negative coordinate.
+    fn try_from(p: Point) -> Result<Self, Self::Error> {
+        match (usize::try_from(p.x), usize::try_from(p.y)) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(p),
+        }
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, k: i64) -> Self {
+        Self::new(self.x * k, self.y * k)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// A signed 3D point; also used as the offset between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    /// The 6 face-adjacent offsets.
+    pub const ADJACENT_6: [Point3; 6] = [
+        Point3::new(0, 0, -1),
+        Point3::new(0, -1, 0),
+        Point3::new(-1, 0, 0),
+        Point3::new(1, 0, 0),
+        Point3::new(0, 1, 0),
+        Point3::new(0, 0, 1),
+    ];
+
+    /// The 26 offsets sharing a face, edge or corner, ordered by `z`, `y`, `x`.
+    pub const ADJACENT_26: [Point3; 26] = {
+        let mut offsets = [Point3::ORIGIN; 26];
+        let mut i = 0;
+        let mut n = 0;
+        while n < 27 {
+            if n != 13 {
+                offsets[i] = Point3::new(n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+                i += 1;
+            }
+            n += 1;
+        }
+        offsets
+    };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Taxicab distance: steps needed when moving in 6 directions.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// King's distance: steps needed when moving in 26 directions.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// Squared Euclidean distance, exact and enough for comparisons.
+    pub fn distance2(self, other: Self) -> i64 {
+        let d = self - other;
+        d.dot(d)
+    }
+
+    pub fn dot(self, other: Self) -> i64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl From<(usize, usize, usize)> for Point3 {
+    fn from((x, y, z): (usize, usize, usize)) -> Self {
+        Self::new(x as i64, y as i64, z as i64)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl TryFrom<Point3> for (usize, usize, usize) {
+    type Error = Point3;
+
+    /// Fails for points with a negative coordinate.
+    fn try_from(p: Point3) -> Result<Self, Self::Error> {
+        match (
+            usize::try_from(p.x),
+            usize::try_from(p.y),
+            usize::try_from(p.z),
+        ) {
+            (Ok(x), Ok(y), Ok(z)) => Ok((x, y, z)),
+            _ => Err(p),
+        }
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Self;
+
+    fn mul(self, k: i64) -> Self {
+        Self::new(self.x * k, self.y * k, self.z * k)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// The 8 compass directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4 orthogonal directions, clockwise from north.
+    pub const ALL_4: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All 8 directions in reading order (row by row, left to right).
+    pub const ALL_8: [Direction; 8] = [
+        Direction::NorthWest,
+        Direction::North,
+        Direction::NorthEast,
+        Direction::West,
+        Direction::East,
+        Direction::SouthWest,
+        Direction::South,
+        Direction::SouthEast,
+    ];
+
+    /// The unit offset of one step in this direction.
+    pub const fn offset(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::NorthEast => Point::new(1, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::South => Point::new(0, 1),
+            Direction::SouthWest => Point::new(-1, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    /// Rotates 45° clockwise.
+    pub const fn clockwise(self) -> Self {
+        match self {
+            Direction::North => Direction::NorthEast,
+            Direction::NorthEast => Direction::East,
+            Direction::East => Direction::SouthEast,
+            Direction::SouthEast => Direction::South,
+            Direction::South => Direction::SouthWest,
+            Direction::SouthWest => Direction::West,
+            Direction::West => Direction::NorthWest,
+            Direction::NorthWest => Direction::North,
+        }
+    }
+
+    /// Rotates 45° counter-clockwise.
+    pub const fn counter_clockwise(self) -> Self {
+        match self {
+            Direction::North => Direction::NorthWest,
+            Direction::NorthEast => Direction::North,
+            Direction::East => Direction::NorthEast,
+            Direction::SouthEast => Direction::East,
+            Direction::South => Direction::SouthEast,
+            Direction::SouthWest => Direction::South,
+            Direction::West => Direction::SouthWest,
+            Direction::NorthWest => Direction::West,
+        }
+    }
+
+    /// Rotates 90° clockwise.
+    pub const fn turn_right(self) -> Self {
+        self.clockwise().clockwise()
+    }
+
+    /// Rotates 90° counter-clockwise.
+    pub const fn turn_left(self) -> Self {
+        self.counter_clockwise().counter_clockwise()
+    }
+
+    pub const fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, -2);
+        let b = Point::new(-3, 4);
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(b - a, Point::new(-4, 6));
+        assert_eq!(a + (b - a) * 2, Point::new(-7, 10));
+    }
+
+    #[test]
+    fn points_in_space() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(4, 6, 3);
+        assert_eq!(a.distance2(b), 25);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.cross(b).dot(a), 0);
+        assert_eq!(-a + b * 2, Point3::new(7, 10, 3));
+
+        assert_eq!(Point3::ADJACENT_26[0], Point3::new(-1, -1, -1));
+        assert!(Point3::ADJACENT_26.iter().all(|&d| d != Point3::ORIGIN));
+        assert!(
+            Point3::ADJACENT_6
+                .iter()
+                .all(|d| Point3::ADJACENT_26.contains(d) && d.manhattan(Point3::ORIGIN) == 1)
+        );
+    }
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthEast.turn_right(), Direction::SouthEast);
+        assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
+        assert!(
+            Direction::ALL_8
+                .iter()
+                .all(|d| d.opposite().offset() == -d.offset())
+        );
+    }
+}
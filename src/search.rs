@@ -0,0 +1,332 @@
+//! Graph search over arbitrary state spaces: BFS, Dijkstra and A*.
+//!
+//! States are anything hashable; the graph is given implicitly by a
+//! successor function. All searches accept several start states and stop
+//! early once `is_goal` accepts a state (pass `|_| false` to explore
+//! everything and get a full distance map).
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::grid::Grid;
+
+/// Distances and predecessors found by a search.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C> {
+    dist: HashMap<S, C>,
+    parent: HashMap<S, S>,
+    goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> SearchResult<S, C> {
+    /// The goal state that stopped the search, if any.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// Cost from the nearest start to the goal.
+    pub fn cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|g| self.distance(g))
+    }
+
+    /// Cost from the nearest start to `state`, if it was reached.
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.dist.get(state).copied()
+    }
+
+    /// Every reached state with its cost.
+    pub fn distances(&self) -> &HashMap<S, C> {
+        &self.dist
+    }
+
+    /// The states from a start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|g| self.path_to(g))
+    }
+
+    /// The states from a start to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.dist.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        while let Some(prev) = self.parent.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search; every step costs 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult {
+        dist: HashMap::new(),
+        parent: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(e) = result.dist.entry(start.clone()) {
+            e.insert(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, d)) = queue.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        for next in successors(&state) {
+            if let Entry::Vacant(e) = result.dist.entry(next.clone()) {
+                e.insert(d + 1);
+                result.parent.insert(next.clone(), state.clone());
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm for non-negative step costs.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// A* search; `heuristic` must never overestimate the remaining cost.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut result = SearchResult {
+        dist: HashMap::new(),
+        parent: HashMap::new(),
+        goal: None,
+    };
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(e) = result.dist.entry(start.clone()) {
+            e.insert(C::default());
+            heap.push(Queued {
+                priority: heuristic(&start),
+                cost: C::default(),
+                state: start,
+            });
+        }
+    }
+
+    while let Some(Queued { cost, state, .. }) = heap.pop() {
+        if result.dist.get(&state).is_some_and(|&best| cost > best) {
+            continue;
+        }
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let improved = result.dist.get(&next).is_none_or(|&best| next_cost < best);
+            if improved {
+                result.dist.insert(next.clone(), next_cost);
+                result.parent.insert(next.clone(), state.clone());
+                heap.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// A heap entry ordered by priority only, lowest first.
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Searches over the 4-neighborhood of a grid.
+impl<T> Grid<T> {
+    /// BFS between cells for which `passable` holds.
+    pub fn bfs(
+        &self,
+        starts: impl IntoIterator<Item = (usize, usize)>,
+        passable: impl Fn(&T) -> bool,
+        is_goal: impl FnMut(&(usize, usize)) -> bool,
+    ) -> SearchResult<(usize, usize), usize> {
+        let passable = &passable;
+        bfs(
+            starts,
+            |&(x, y)| {
+                self.neighbors_4(x, y)
+                    .filter(move |(_, v)| passable(v))
+                    .map(|(p, _)| p)
+            },
+            is_goal,
+        )
+    }
+
+    /// Dijkstra where entering a cell costs `enter_cost`, `None` meaning impassable.
+    pub fn dijkstra(
+        &self,
+        starts: impl IntoIterator<Item = (usize, usize)>,
+        enter_cost: impl Fn(&T) -> Option<u64>,
+        is_goal: impl FnMut(&(usize, usize)) -> bool,
+    ) -> SearchResult<(usize, usize), u64> {
+        dijkstra(
+            starts,
+            |&p| self.weighted_neighbors(p, &enter_cost),
+            is_goal,
+        )
+    }
+
+    /// A* towards `goal` with the Manhattan distance as heuristic, so every
+    /// `enter_cost` must be at least 1.
+    pub fn astar(
+        &self,
+        starts: impl IntoIterator<Item = (usize, usize)>,
+        goal: (usize, usize),
+        enter_cost: impl Fn(&T) -> Option<u64>,
+    ) -> SearchResult<(usize, usize), u64> {
+        astar(
+            starts,
+            |&p| self.weighted_neighbors(p, &enter_cost),
+            |&(x, y)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u64,
+            |&p| p == goal,
+        )
+    }
+
+    fn weighted_neighbors<'a>(
+        &'a self,
+        (x, y): (usize, usize),
+        enter_cost: &'a impl Fn(&T) -> Option<u64>,
+    ) -> impl Iterator<Item = ((usize, usize), u64)> + 'a {
+        self.neighbors_4(x, y)
+            .filter_map(move |(p, v)| enter_cost(v).map(|c| (p, c)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "
+        S.#.....
+        .##.###.
+        ....#.G.
+        ";
+
+    fn maze() -> (Grid<char>, (usize, usize), (usize, usize)) {
+        let grid = Grid::parse(MAZE, Ok::<_, ()>).unwrap();
+        let find = |c| grid.coords().find(|&p| grid[p] == c).unwrap();
+        let (start, goal) = (find('S'), find('G'));
+        (grid, start, goal)
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let (grid, start, goal) = maze();
+        let result = grid.bfs([start], |&c| c != '#', |&p| p == goal);
+        assert_eq!(result.cost(), Some(14));
+
+        let path = result.path().unwrap();
+        assert_eq!(path.len(), 15);
+        assert_eq!((path[0], path[14]), (start, goal));
+    }
+
+    #[test]
+    fn bfs_multi_source_distance_map() {
+        let in_range = |n: &i32| (0..=10).contains(n);
+        let result = bfs(
+            [0, 10],
+            |&n| [n - 1, n + 1].into_iter().filter(in_range),
+            |_| false,
+        );
+        assert_eq!(result.goal(), None);
+        assert_eq!(result.distance(&5), Some(5));
+        assert_eq!(result.distance(&7), Some(3));
+        assert_eq!(result.path_to(&8), Some(vec![10, 9, 8]));
+        assert_eq!(result.distances().len(), 11);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        let (grid, start, goal) = maze();
+        let cost = |&c: &char| (c != '#').then_some(if c == '.' { 2 } else { 1 });
+
+        let d = grid.dijkstra([start], cost, |&p| p == goal);
+        let a = grid.astar([start], goal, cost);
+        assert_eq!(d.cost(), Some(27));
+        assert_eq!(a.cost(), d.cost());
+        assert_eq!(a.path().unwrap().last(), Some(&goal));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let result = dijkstra(
+            [1u32],
+            |&n| [(n * 2, 1u64)].into_iter().filter(|(m, _)| *m < 100),
+            |&n| n == 3,
+        );
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.path(), None);
+        assert_eq!(result.distance(&64), Some(6));
+    }
+}
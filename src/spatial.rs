@@ -0,0 +1,406 @@
+//! Nearest-neighbor queries over integer points with a k-d tree.
+//!
+//! Distances are squared Euclidean, so they stay exact integers.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::disjoint_set::DisjointSet;
+use crate::mst::Edge;
+use crate::point::{Point, Point3};
+
+/// A point with integer coordinates along a fixed number of axes.
+pub trait Spatial: Copy {
+    const DIMS: usize;
+
+    fn coord(&self, axis: usize) -> i64;
+}
+
+impl Spatial for Point {
+    const DIMS: usize = 2;
+
+    fn coord(&self, axis: usize) -> i64 {
+        [self.x, self.y][axis]
+    }
+}
+
+impl Spatial for Point3 {
+    const DIMS: usize = 3;
+
+    fn coord(&self, axis: usize) -> i64 {
+        [self.x, self.y, self.z][axis]
+    }
+}
+
+fn distance2<P: Spatial>(a: &P, b: &P) -> i64 {
+    (0..P::DIMS)
+        .map(|axis| (a.coord(axis) - b.coord(axis)).pow(2))
+        .sum()
+}
+
+/// A balanced k-d tree over a fixed set of points, referred to by their
+/// index in the original list.
+#[derive(Debug, Clone)]
+pub struct KdTree<P> {
+    points: Vec<P>,
+    /// Point indices laid out as an implicit tree: each range splits at its
+    /// middle entry, smaller coordinates to the left.
+    order: Vec<usize>,
+}
+
+impl<P: Spatial> KdTree<P> {
+    pub fn new(points: impl IntoIterator<Item = P>) -> Self {
+        let points: Vec<P> = points.into_iter().collect();
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(&points, &mut order, 0);
+        Self { points, order }
+    }
+
+    fn build(points: &[P], order: &mut [usize], depth: usize) {
+        if order.len() <= 1 {
+            return;
+        }
+        let axis = depth % P::DIMS;
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by_key(mid, |&i| points[i].coord(axis));
+
+        let (left, right) = order.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    /// Up to `k` points accepted by `keep` that are nearest to `query`, as
+    /// `(squared distance, index)` pairs in increasing order.
+    pub fn k_nearest(&self, query: P, k: usize, keep: impl Fn(usize) -> bool) -> Vec<(i64, usize)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(&query, k, &keep, &mut best, 0, self.len(), 0);
+        }
+        best.into_sorted_vec()
+    }
+
+    /// The point accepted by `keep` nearest to `query`.
+    pub fn nearest(&self, query: P, keep: impl Fn(usize) -> bool) -> Option<(i64, usize)> {
+        self.k_nearest(query, 1, keep).pop()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        query: &P,
+        k: usize,
+        keep: &impl Fn(usize) -> bool,
+        best: &mut BinaryHeap<(i64, usize)>,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let i = self.order[mid];
+
+        if keep(i) {
+            best.push((distance2(query, &self.points[i]), i));
+            if best.len() > k {
+                best.pop();
+            }
+        }
+
+        let axis = depth % P::DIMS;
+        let diff = query.coord(axis) - self.points[i].coord(axis);
+        let (near, far) = if diff < 0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+
+        self.search(query, k, keep, best, near.0, near.1, depth + 1);
+        // Ties can hide on either side of the split, so only a strictly
+        // farther plane rules the other side out.
+        if best.len() < k || diff * diff <= best.peek().unwrap().0 {
+            self.search(query, k, keep, best, far.0, far.1, depth + 1);
+        }
+    }
+
+    /// Every pair of points, shortest first, found lazily. Equal distances
+    /// keep the order of `(a, b)` with `a < b`, as in
+    /// [`sorted_pairs`](crate::mst::sorted_pairs).
+    ///
+    /// Memory grows with the number of pairs taken, not the number of
+    /// pairs there are. Meant for the first few pairs: reaching a far
+    /// outlier takes almost every pair, so use
+    /// [`Self::spanning_tree`] to connect everything.
+    pub fn closest_pairs(&self) -> ClosestPairs<'_, P> {
+        let mut pairs = ClosestPairs {
+            tree: self,
+            heap: BinaryHeap::with_capacity(self.len()),
+            pending: vec![Vec::new().into_iter(); self.len()],
+            taken: vec![0; self.len()],
+        };
+        (0..self.len()).for_each(|a| pairs.refill(a));
+        pairs
+    }
+
+    /// The minimum spanning tree edges by Borůvka's algorithm, shortest
+    /// first. Equal distances are broken by `(a, b)` with `a < b`, so the
+    /// tree is the one Kruskal finds over
+    /// [`sorted_pairs`](crate::mst::sorted_pairs).
+    pub fn spanning_tree(&self) -> Vec<Edge<i64>> {
+        self.boruvka().0
+    }
+
+    /// The spanning tree edges and the number of tree nodes visited.
+    fn boruvka(&self) -> (Vec<Edge<i64>>, usize) {
+        let n = self.len();
+        let mut sets = DisjointSet::new(n);
+        let mut edges = Vec::with_capacity(n.saturating_sub(1));
+        let mut uniform = vec![MIXED; n];
+        let mut visits = 0;
+
+        while sets.components() > 1 {
+            let comp: Vec<usize> = (0..n).map(|i| sets.find(i)).collect();
+            self.label(&comp, &mut uniform, 0, n);
+
+            // The cheapest edge out of each component, as (weight, a, b).
+            let mut cheapest: Vec<Option<(i64, usize, usize)>> = vec![None; n];
+            for a in 0..n {
+                let c = comp[a];
+                let mut search = Foreign {
+                    comp: &comp,
+                    uniform: &uniform,
+                    bound: cheapest[c].map_or(i64::MAX, |e| e.0),
+                    best: None,
+                    visits: 0,
+                };
+                self.search_foreign(a, &mut search, 0, n, 0);
+                visits += search.visits;
+                if let Some((d, b)) = search.best {
+                    let edge = (d, a.min(b), a.max(b));
+                    if cheapest[c].is_none_or(|e| edge < e) {
+                        cheapest[c] = Some(edge);
+                    }
+                }
+            }
+
+            for (weight, a, b) in cheapest.into_iter().flatten() {
+                if sets.union(a, b) {
+                    edges.push(Edge { a, b, weight });
+                }
+            }
+        }
+
+        edges.sort_unstable_by_key(|e| (e.weight, e.a, e.b));
+        (edges, visits)
+    }
+
+    /// Records at each node the component of every point in its subtree,
+    /// or [`MIXED`], and returns it.
+    fn label(&self, comp: &[usize], uniform: &mut [usize], lo: usize, hi: usize) -> usize {
+        let mid = (lo + hi) / 2;
+        let c = comp[self.order[mid]];
+        let left = if lo < mid {
+            self.label(comp, uniform, lo, mid)
+        } else {
+            c
+        };
+        let right = if mid + 1 < hi {
+            self.label(comp, uniform, mid + 1, hi)
+        } else {
+            c
+        };
+        uniform[mid] = if left == c && right == c { c } else { MIXED };
+        uniform[mid]
+    }
+
+    /// The nearest point to point `a` outside its component, skipping
+    /// subtrees that lie wholly inside it.
+    fn search_foreign(&self, a: usize, search: &mut Foreign, lo: usize, hi: usize, depth: usize) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if search.uniform[mid] == search.comp[a] {
+            return;
+        }
+        search.visits += 1;
+
+        let i = self.order[mid];
+        if search.comp[i] != search.comp[a] {
+            let d = distance2(&self.points[a], &self.points[i]);
+            if d <= search.bound && search.best.is_none_or(|best| (d, i) < best) {
+                search.best = Some((d, i));
+            }
+        }
+
+        let axis = depth % P::DIMS;
+        let diff = self.points[a].coord(axis) - self.points[i].coord(axis);
+        let (near, far) = if diff < 0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+
+        self.search_foreign(a, search, near.0, near.1, depth + 1);
+        let limit = search.best.map_or(search.bound, |best| best.0);
+        if diff * diff <= limit {
+            self.search_foreign(a, search, far.0, far.1, depth + 1);
+        }
+    }
+}
+
+/// Marks a k-d tree node whose subtree spans several components.
+const MIXED: usize = usize::MAX;
+
+/// State of one search in [`KdTree::spanning_tree`]: the nearest point in
+/// another component, no farther than `bound`.
+struct Foreign<'a> {
+    comp: &'a [usize],
+    uniform: &'a [usize],
+    bound: i64,
+    best: Option<(i64, usize)>,
+    visits: usize,
+}
+
+/// Iterator returned by [`KdTree::closest_pairs`].
+///
+/// Each point `a` keeps a batch of its nearest points with a larger index;
+/// a heap holds the next pair of every point, and a point whose batch runs
+/// out asks the tree for one twice as large.
+#[derive(Debug, Clone)]
+pub struct ClosestPairs<'t, P> {
+    tree: &'t KdTree<P>,
+    heap: BinaryHeap<Reverse<(i64, usize, usize)>>,
+    pending: Vec<std::vec::IntoIter<(i64, usize)>>,
+    /// Number of pairs yielded so far for each point.
+    taken: Vec<usize>,
+}
+
+impl<P: Spatial> ClosestPairs<'_, P> {
+    const BATCH: usize = 4;
+
+    fn refill(&mut self, a: usize) {
+        let taken = self.taken[a];
+        let query = self.tree.points[a];
+        let mut batch = self
+            .tree
+            .k_nearest(query, taken * 2 + Self::BATCH, |b| b > a);
+
+        batch.drain(..taken.min(batch.len()));
+        self.pending[a] = batch.into_iter();
+        self.push_next(a);
+    }
+
+    fn push_next(&mut self, a: usize) -> bool {
+        match self.pending[a].next() {
+            Some((d, b)) => {
+                self.heap.push(Reverse((d, a, b)));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<P: Spatial> Iterator for ClosestPairs<'_, P> {
+    type Item = Edge<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((weight, a, b)) = self.heap.pop()?;
+        self.taken[a] += 1;
+        if !self.push_next(a) {
+            self.refill(a);
+        }
+        Some(Edge { a, b, weight })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mst::{SpanningForest, prim, sorted_pairs};
+
+    /// Deterministic points in a small cube, so many distances tie.
+    fn points(n: usize, side: u64) -> Vec<Point3> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % side) as i64
+        };
+        (0..n)
+            .map(|_| Point3::new(next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn nearest_matches_brute_force() {
+        let pts = points(200, 50);
+        let tree = KdTree::new(pts.clone());
+        for (q, query) in pts.iter().enumerate().step_by(17) {
+            let mut expected: Vec<(i64, usize)> = (0..pts.len())
+                .filter(|&i| i != q)
+                .map(|i| (query.distance2(pts[i]), i))
+                .collect();
+            expected.sort_unstable();
+            expected.truncate(7);
+            assert_eq!(tree.k_nearest(*query, 7, |i| i != q), expected);
+        }
+    }
+
+    #[test]
+    fn closest_pairs_match_sorted_pairs() {
+        let pts = points(120, 8);
+        let tree = KdTree::new(pts.clone());
+        let lazy: Vec<_> = tree.closest_pairs().collect();
+        assert_eq!(lazy, sorted_pairs(&pts, |a, b| a.distance2(*b)));
+
+        let few: Vec<_> = tree.closest_pairs().take(10).collect();
+        assert_eq!(few, lazy[..10]);
+    }
+
+    #[test]
+    fn spanning_tree_matches_kruskal() {
+        let pts = points(150, 8);
+        let tree = KdTree::new(pts.clone());
+        let forest = SpanningForest::of_points(&pts, |a, b| a.distance2(*b));
+        assert_eq!(tree.spanning_tree(), forest.edges());
+    }
+
+    #[test]
+    fn spanning_tree_reaches_outlier_cheaply() {
+        let mut pts = points(5000, 1000);
+        pts.push(Point3::new(10_000_000, 0, 0));
+        let tree = KdTree::new(pts.clone());
+        let (edges, visits) = tree.boruvka();
+
+        let weight = |edges: &[Edge<i64>]| edges.iter().map(|e| e.weight).sum::<i64>();
+        assert_eq!(edges.len(), pts.len() - 1);
+        assert_eq!(edges.last().unwrap().b, 5000);
+        assert_eq!(weight(&edges), weight(&prim(&pts, |a, b| a.distance2(*b))));
+        // Far below the 12.5 million pairs `closest_pairs` needs to reach
+        // the outlier.
+        assert!(visits < 400 * pts.len(), "{visits} nodes visited");
+    }
+
+    #[test]
+    fn planar_points() {
+        let tree = KdTree::new([(0, 0), (5, 5), (1, 0), (9, 9)].map(|(x, y)| Point::new(x, y)));
+        assert_eq!(tree.nearest(Point::new(4, 4), |_| true), Some((2, 1)));
+        assert_eq!(tree.closest_pairs().count(), 6);
+    }
+}